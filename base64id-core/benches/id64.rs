@@ -18,5 +18,46 @@ fn decode_i64_bench(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, encode_i64_bench, decode_i64_bench,);
+fn encode_i64_bytes_bench(c: &mut Criterion) {
+    c.bench_function("encode i64 as [u8; 11]", |b| {
+        b.iter(|| black_box(base64::encode_i64_bytes(25519i64)))
+    });
+}
+
+fn decode_i64_bytes_bench(c: &mut Criterion) {
+    c.bench_function("decode [u8; 11] as i64", |b| {
+        b.iter(|| black_box(base64::decode_i64_bytes(b"AAAAAAAAY68")))
+    });
+}
+
+fn encode_slice_i64_bench(c: &mut Criterion) {
+    let input: Vec<i64> = (0..1000).map(|i| i * 25519).collect();
+    let mut output = vec![0u8; input.len() * 11];
+
+    c.bench_function("encode 1000 i64 as [u8; 11000]", |b| {
+        b.iter(|| black_box(base64::encode_slice_i64(&input, &mut output)))
+    });
+}
+
+fn decode_slice_i64_bench(c: &mut Criterion) {
+    let input: Vec<i64> = (0..1000).map(|i| i * 25519).collect();
+    let mut encoded = vec![0u8; input.len() * 11];
+    let mut output = vec![0i64; input.len()];
+
+    base64::encode_slice_i64(&input, &mut encoded).unwrap();
+
+    c.bench_function("decode [u8; 11000] as 1000 i64", |b| {
+        b.iter(|| black_box(base64::decode_slice_i64(&encoded, &mut output)))
+    });
+}
+
+criterion_group!(
+    benches,
+    encode_i64_bench,
+    decode_i64_bench,
+    encode_i64_bytes_bench,
+    decode_i64_bytes_bench,
+    encode_slice_i64_bench,
+    decode_slice_i64_bench,
+);
 criterion_main!(benches);
@@ -0,0 +1,182 @@
+use core::fmt;
+
+/// Marks bytes within [`Alphabet::decode`] which are not part of the alphabet
+const INVALID: u8 = 0xFF;
+
+/// A 64 character alphabet used for encoding and decoding
+///
+/// The builtin alphabets are [`Alphabet::BASE64URL`], [`Alphabet::STANDARD`] and [`Alphabet::SORTABLE`].
+/// Custom alphabets are created with [`Alphabet::new`], which should be evaluated within a `const` so that invalid alphabets are rejected at compile time:
+/// ```
+/// use base64id_core::Alphabet;
+///
+/// const SHUFFLED: Alphabet =
+///     Alphabet::new("_-9876543210zyxwvutsrqponmlkjihgfedcbaZYXWVUTSRQPONMLKJIHGFEDCBA");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Alphabet {
+    symbols: [u8; 64],
+    table: [u8; 256],
+}
+
+impl Alphabet {
+    /// The [base64url](https://datatracker.ietf.org/doc/html/rfc4648#section-5) alphabet
+    ///
+    /// This is the default alphabet.
+    pub const BASE64URL: Alphabet =
+        Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+
+    /// The [standard base64](https://datatracker.ietf.org/doc/html/rfc4648#section-4) alphabet
+    pub const STANDARD: Alphabet =
+        Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+
+    /// An alphabet ordered by ASCII value
+    ///
+    /// Fixed length strings encoded with this alphabet sort in the same order as the unsigned integers they represent.
+    /// See the `encode_*_sortable` functions in the [`base64`](crate::base64) module for signed integers.
+    pub const SORTABLE: Alphabet =
+        Alphabet::new("-0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ_abcdefghijklmnopqrstuvwxyz");
+
+    /// Create a new alphabet from 64 unique characters
+    ///
+    /// The character at index `n` represents the 6 bit value `n`.
+    ///
+    /// # Panics
+    /// Panics if `symbols` is not exactly 64 characters long,
+    /// contains a character that is not printable ASCII,
+    /// or contains the same character more than once.
+    #[must_use]
+    pub const fn new(symbols: &str) -> Alphabet {
+        let bytes = symbols.as_bytes();
+
+        if bytes.len() != 64 {
+            panic!("invalid alphabet. expected exactly 64 characters");
+        }
+
+        let mut alphabet = Alphabet {
+            symbols: [0; 64],
+            table: [INVALID; 256],
+        };
+
+        let mut i = 0;
+        while i < 64 {
+            let b = bytes[i];
+
+            if !b.is_ascii_graphic() {
+                panic!("invalid alphabet. expected only printable ASCII characters");
+            }
+
+            if alphabet.table[b as usize] != INVALID {
+                panic!("invalid alphabet. characters must not be repeated");
+            }
+
+            alphabet.symbols[i] = b;
+            alphabet.table[b as usize] = i as u8;
+            i += 1;
+        }
+
+        alphabet
+    }
+
+    /// Get the ASCII character which represents the 6 bit value `sextet`
+    ///
+    /// Only the lower 6 bits of `sextet` are used.
+    #[must_use]
+    pub const fn encode(&self, sextet: u8) -> u8 {
+        self.symbols[(sextet & 0b0011_1111) as usize]
+    }
+
+    /// Get the 6 bit value represented by the ASCII character `byte`
+    ///
+    /// Returns `None` if `byte` is not within this alphabet.
+    #[must_use]
+    pub const fn decode(&self, byte: u8) -> Option<u8> {
+        match self.table[byte as usize] {
+            INVALID => None,
+            sextet => Some(sextet),
+        }
+    }
+
+    /// Get all 64 characters of this alphabet, ordered by the value they represent
+    #[must_use]
+    pub const fn symbols(&self) -> &[u8; 64] {
+        &self.symbols
+    }
+
+    /// Get all 64 characters of this alphabet as a `str`
+    #[must_use]
+    pub const fn as_str(&self) -> &str {
+        match core::str::from_utf8(&self.symbols) {
+            Ok(s) => s,
+            Err(_) => panic!("alphabet contains non-ASCII characters"),
+        }
+    }
+}
+
+impl fmt::Debug for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Alphabet").field(&self.as_str()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Alphabet;
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        for alphabet in [Alphabet::BASE64URL, Alphabet::STANDARD, Alphabet::SORTABLE] {
+            for sextet in 0..64 {
+                let c = alphabet.encode(sextet);
+                assert_eq!(alphabet.decode(c), Some(sextet));
+            }
+        }
+    }
+
+    #[test]
+    fn decode_outside_alphabet() {
+        assert_eq!(Alphabet::BASE64URL.decode(b'+'), None);
+        assert_eq!(Alphabet::BASE64URL.decode(b'='), None);
+        assert_eq!(Alphabet::STANDARD.decode(b'-'), None);
+        assert_eq!(Alphabet::STANDARD.decode(0xE9), None);
+    }
+
+    #[test]
+    fn builtin_symbols() {
+        assert_eq!(
+            Alphabet::BASE64URL.as_str(),
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+        );
+        assert_eq!(
+            Alphabet::STANDARD.as_str(),
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"
+        );
+    }
+
+    #[test]
+    fn sortable_symbols_ascending() {
+        let symbols = Alphabet::SORTABLE.symbols();
+
+        for i in 1..64 {
+            assert!(symbols[i - 1] < symbols[i]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "expected exactly 64 characters")]
+    fn new_invalid_length() {
+        let _ = Alphabet::new("ABC");
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be repeated")]
+    fn new_repeated_character() {
+        let _ = Alphabet::new("AACDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+    }
+
+    #[test]
+    #[should_panic(expected = "printable ASCII")]
+    fn new_non_printable_character() {
+        let _ = Alphabet::new(" BCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+    }
+}
@@ -7,95 +7,209 @@
 //             Base64 - 4 Chars            //
 // ####################################### //
 
-use crate::Error;
-
-const ALPHABET_BASE64URL: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
-const ALPHABET_BASE64URL_BYTES: &[u8] = ALPHABET_BASE64URL.as_bytes();
-
-fn decode_char(c: char) -> Result<u8, Error> {
-    let idx = match c {
-        'A' => 0,
-        'B' => 1,
-        'C' => 2,
-        'D' => 3,
-        'E' => 4,
-        'F' => 5,
-        'G' => 6,
-        'H' => 7,
-        'I' => 8,
-        'J' => 9,
-        'K' => 10,
-        'L' => 11,
-        'M' => 12,
-        'N' => 13,
-        'O' => 14,
-        'P' => 15,
-        'Q' => 16,
-        'R' => 17,
-        'S' => 18,
-        'T' => 19,
-        'U' => 20,
-        'V' => 21,
-        'W' => 22,
-        'X' => 23,
-        'Y' => 24,
-        'Z' => 25,
-        'a' => 26,
-        'b' => 27,
-        'c' => 28,
-        'd' => 29,
-        'e' => 30,
-        'f' => 31,
-        'g' => 32,
-        'h' => 33,
-        'i' => 34,
-        'j' => 35,
-        'k' => 36,
-        'l' => 37,
-        'm' => 38,
-        'n' => 39,
-        'o' => 40,
-        'p' => 41,
-        'q' => 42,
-        'r' => 43,
-        's' => 44,
-        't' => 45,
-        'u' => 46,
-        'v' => 47,
-        'w' => 48,
-        'x' => 49,
-        'y' => 50,
-        'z' => 51,
-        '0' => 52,
-        '1' => 53,
-        '2' => 54,
-        '3' => 55,
-        '4' => 56,
-        '5' => 57,
-        '6' => 58,
-        '7' => 59,
-        '8' => 60,
-        '9' => 61,
-        '-' => 62,
-        '_' => 63,
-        _ => return Err(Error::InvalidCharacter),
+use crate::{error::AllowedCharacters, Alphabet, Error};
+
+mod batch;
+mod checksum;
+mod lenient;
+mod pattern;
+mod prefix;
+
+pub use batch::*;
+pub use checksum::*;
+pub use lenient::*;
+pub use pattern::*;
+pub use prefix::*;
+
+/// `?` operator for use within `const fn`
+macro_rules! const_try {
+    ($expr:expr) => {
+        match $expr {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        }
     };
+}
+
+/// Convert an array of `char` to ASCII bytes
+///
+/// Returns [`Error::InvalidCharacter`] if any `char` is not ASCII.
+pub const fn ascii_from_chars<const N: usize>(input: [char; N]) -> Result<[u8; N], Error> {
+    let mut c: [u8; N] = [0; N];
+
+    let mut i = 0;
+    while i < N {
+        if !input[i].is_ascii() {
+            return Err(Error::InvalidCharacter {
+                index: i,
+                character: input[i],
+            });
+        }
+
+        c[i] = input[i] as u8;
+        i += 1;
+    }
+
+    Ok(c)
+}
+
+const fn decode_bytes<const N: usize>(input: &[u8], alphabet: &Alphabet) -> Result<[u8; N], Error> {
+    if input.len() != N {
+        return Err(Error::InvalidLength {
+            actual: input.len(),
+            expected: N,
+        });
+    }
+
+    let mut c: [u8; N] = [0; N];
+
+    let mut i = 0;
+    while i < N {
+        c[i] = match alphabet.decode(input[i]) {
+            Some(sextet) => sextet,
+            None => {
+                return Err(Error::InvalidCharacter {
+                    index: i,
+                    character: input[i] as char,
+                })
+            }
+        };
+        i += 1;
+    }
 
-    Ok(idx)
+    Ok(c)
 }
 
 #[must_use]
-pub fn encode_i128(input: i128) -> [char; 22] {
-    encode_128(input.to_be_bytes())
+const fn encode_sextets<const N: usize>(sextets: [u8; N], alphabet: &Alphabet) -> [u8; N] {
+    let mut c: [u8; N] = [0; N];
+
+    let mut i = 0;
+    while i < N {
+        c[i] = alphabet.encode(sextets[i]);
+        i += 1;
+    }
+
+    c
 }
 
 #[must_use]
-pub fn encode_u128(input: u128) -> [char; 22] {
-    encode_128(input.to_be_bytes())
+const fn ascii_to_chars<const N: usize>(input: [u8; N]) -> [char; N] {
+    let mut c: [char; N] = ['\0'; N];
+
+    let mut i = 0;
+    while i < N {
+        c[i] = input[i] as char;
+        i += 1;
+    }
+
+    c
 }
 
+const fn write_into<const N: usize>(encoded: [u8; N], output: &mut [u8]) -> Result<(), Error> {
+    if output.len() < N {
+        return Err(Error::InvalidLength {
+            actual: output.len(),
+            expected: N,
+        });
+    }
+
+    let mut i = 0;
+    while i < N {
+        output[i] = encoded[i];
+        i += 1;
+    }
+
+    Ok(())
+}
+
+/// Error for the out of bounds last character of `sextets`
+#[must_use]
+const fn out_of_bounds<const N: usize>(sextets: &[u8; N], alphabet: &Alphabet) -> Error {
+    Error::OutOfBoundsCharacter {
+        index: N - 1,
+        character: alphabet.encode(sextets[N - 1]) as char,
+        allowed: AllowedCharacters::new(alphabet, (N as u32 * 6) % 8),
+    }
+}
+
+/// Replace byte oriented details of an [`Error`] with those of the `str` which was decoded
+///
+/// Lengths are counted in characters rather than bytes, and invalid characters are decoded from UTF-8.
+#[must_use]
+pub fn error_for_str(error: Error, input: &str) -> Error {
+    match error {
+        Error::InvalidLength { expected, .. } => match input.chars().count() {
+            // the length only differs in bytes, so the first non-ASCII character is at fault
+            actual if actual == expected => match input.char_indices().find(|(_, c)| !c.is_ascii())
+            {
+                Some((index, character)) => Error::InvalidCharacter { index, character },
+                None => error,
+            },
+            actual => Error::InvalidLength { actual, expected },
+        },
+        // all bytes before index are ASCII, so index is on a char boundary
+        Error::InvalidCharacter { index, character } => Error::InvalidCharacter {
+            index,
+            character: match input.get(index..).and_then(|s| s.chars().next()) {
+                Some(c) => c,
+                None => character,
+            },
+        },
+        e => e,
+    }
+}
+
+#[must_use]
+pub const fn encode_i128(input: i128) -> [char; 22] {
+    ascii_to_chars(encode_i128_bytes(input))
+}
+
+#[must_use]
+pub const fn encode_u128(input: u128) -> [char; 22] {
+    ascii_to_chars(encode_u128_bytes(input))
+}
+
+/// Encode an `i128` as 22 base64url ASCII bytes
 #[must_use]
-fn encode_128(bytes: [u8; 16]) -> [char; 22] {
+pub const fn encode_i128_bytes(input: i128) -> [u8; 22] {
+    encode_128(input.to_be_bytes(), &Alphabet::BASE64URL)
+}
+
+/// Encode a `u128` as 22 base64url ASCII bytes
+#[must_use]
+pub const fn encode_u128_bytes(input: u128) -> [u8; 22] {
+    encode_128(input.to_be_bytes(), &Alphabet::BASE64URL)
+}
+
+/// Encode an `i128` as 22 ASCII bytes of the given alphabet
+#[must_use]
+pub const fn encode_i128_with(input: i128, alphabet: &Alphabet) -> [u8; 22] {
+    encode_128(input.to_be_bytes(), alphabet)
+}
+
+/// Encode a `u128` as 22 ASCII bytes of the given alphabet
+#[must_use]
+pub const fn encode_u128_with(input: u128, alphabet: &Alphabet) -> [u8; 22] {
+    encode_128(input.to_be_bytes(), alphabet)
+}
+
+/// Encode an `i128` into the first 22 bytes of `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than 22 bytes.
+pub const fn encode_i128_into(input: i128, output: &mut [u8]) -> Result<(), Error> {
+    write_into(encode_i128_bytes(input), output)
+}
+
+/// Encode a `u128` into the first 22 bytes of `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than 22 bytes.
+pub const fn encode_u128_into(input: u128, output: &mut [u8]) -> Result<(), Error> {
+    write_into(encode_u128_bytes(input), output)
+}
+
+#[must_use]
+const fn encode_128(bytes: [u8; 16], alphabet: &Alphabet) -> [u8; 22] {
     let p1 = encode_quantum([bytes[0], bytes[1], bytes[2]]);
     let p2 = encode_quantum([bytes[3], bytes[4], bytes[5]]);
     let p3 = encode_quantum([bytes[6], bytes[7], bytes[8]]);
@@ -113,22 +227,60 @@ fn encode_128(bytes: [u8; 16]) -> [char; 22] {
         p6[0], p6[1],
     ];
 
-    product.map(|d| char::from(ALPHABET_BASE64URL_BYTES[usize::from(d)]))
+    encode_sextets(product, alphabet)
+}
+
+#[must_use]
+pub const fn encode_i64(input: i64) -> [char; 11] {
+    ascii_to_chars(encode_i64_bytes(input))
+}
+
+#[must_use]
+pub const fn encode_u64(input: u64) -> [char; 11] {
+    ascii_to_chars(encode_u64_bytes(input))
 }
 
+/// Encode an `i64` as 11 base64url ASCII bytes
 #[must_use]
-pub fn encode_i64(input: i64) -> [char; 11] {
-    encode_64(input.to_be_bytes())
+pub const fn encode_i64_bytes(input: i64) -> [u8; 11] {
+    encode_64(input.to_be_bytes(), &Alphabet::BASE64URL)
 }
 
+/// Encode a `u64` as 11 base64url ASCII bytes
 #[must_use]
-pub fn encode_u64(input: u64) -> [char; 11] {
-    encode_64(input.to_be_bytes())
+pub const fn encode_u64_bytes(input: u64) -> [u8; 11] {
+    encode_64(input.to_be_bytes(), &Alphabet::BASE64URL)
+}
+
+/// Encode an `i64` as 11 ASCII bytes of the given alphabet
+#[must_use]
+pub const fn encode_i64_with(input: i64, alphabet: &Alphabet) -> [u8; 11] {
+    encode_64(input.to_be_bytes(), alphabet)
+}
+
+/// Encode a `u64` as 11 ASCII bytes of the given alphabet
+#[must_use]
+pub const fn encode_u64_with(input: u64, alphabet: &Alphabet) -> [u8; 11] {
+    encode_64(input.to_be_bytes(), alphabet)
+}
+
+/// Encode an `i64` into the first 11 bytes of `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than 11 bytes.
+pub const fn encode_i64_into(input: i64, output: &mut [u8]) -> Result<(), Error> {
+    write_into(encode_i64_bytes(input), output)
+}
+
+/// Encode a `u64` into the first 11 bytes of `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than 11 bytes.
+pub const fn encode_u64_into(input: u64, output: &mut [u8]) -> Result<(), Error> {
+    write_into(encode_u64_bytes(input), output)
 }
 
 #[must_use]
 #[rustfmt::skip]
-fn encode_64(bytes: [u8; 8]) -> [char; 11] {
+const fn encode_64(bytes: [u8; 8], alphabet: &Alphabet) -> [u8; 11] {
     let p1 = encode_quantum([bytes[0], bytes[1], bytes[2]]);
     let p2 = encode_quantum([bytes[3], bytes[4], bytes[5]]);
     let p3 = encode_partial_16([bytes[6], bytes[7]]);
@@ -139,22 +291,60 @@ fn encode_64(bytes: [u8; 8]) -> [char; 11] {
         p3[0], p3[1], p3[2],
     ];
 
-    product.map(|d| char::from(ALPHABET_BASE64URL_BYTES[usize::from(d)]))
+    encode_sextets(product, alphabet)
+}
+
+#[must_use]
+pub const fn encode_i32(input: i32) -> [char; 6] {
+    ascii_to_chars(encode_i32_bytes(input))
+}
+
+#[must_use]
+pub const fn encode_u32(input: u32) -> [char; 6] {
+    ascii_to_chars(encode_u32_bytes(input))
+}
+
+/// Encode an `i32` as 6 base64url ASCII bytes
+#[must_use]
+pub const fn encode_i32_bytes(input: i32) -> [u8; 6] {
+    encode_32(input.to_be_bytes(), &Alphabet::BASE64URL)
 }
 
+/// Encode a `u32` as 6 base64url ASCII bytes
 #[must_use]
-pub fn encode_i32(input: i32) -> [char; 6] {
-    encode_32(input.to_be_bytes())
+pub const fn encode_u32_bytes(input: u32) -> [u8; 6] {
+    encode_32(input.to_be_bytes(), &Alphabet::BASE64URL)
 }
 
+/// Encode an `i32` as 6 ASCII bytes of the given alphabet
 #[must_use]
-pub fn encode_u32(input: u32) -> [char; 6] {
-    encode_32(input.to_be_bytes())
+pub const fn encode_i32_with(input: i32, alphabet: &Alphabet) -> [u8; 6] {
+    encode_32(input.to_be_bytes(), alphabet)
+}
+
+/// Encode a `u32` as 6 ASCII bytes of the given alphabet
+#[must_use]
+pub const fn encode_u32_with(input: u32, alphabet: &Alphabet) -> [u8; 6] {
+    encode_32(input.to_be_bytes(), alphabet)
+}
+
+/// Encode an `i32` into the first 6 bytes of `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than 6 bytes.
+pub const fn encode_i32_into(input: i32, output: &mut [u8]) -> Result<(), Error> {
+    write_into(encode_i32_bytes(input), output)
+}
+
+/// Encode a `u32` into the first 6 bytes of `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than 6 bytes.
+pub const fn encode_u32_into(input: u32, output: &mut [u8]) -> Result<(), Error> {
+    write_into(encode_u32_bytes(input), output)
 }
 
 #[must_use]
 #[rustfmt::skip]
-fn encode_32(bytes: [u8; 4]) -> [char; 6] {
+const fn encode_32(bytes: [u8; 4], alphabet: &Alphabet) -> [u8; 6] {
     let p1 = encode_quantum([bytes[0], bytes[1], bytes[2]]);
     let p2 = encode_partial_8(bytes[3]);
 
@@ -163,50 +353,109 @@ fn encode_32(bytes: [u8; 4]) -> [char; 6] {
         p2[0], p2[1],
     ];
 
-    product.map(|d| char::from(ALPHABET_BASE64URL_BYTES[usize::from(d)]))
+    encode_sextets(product, alphabet)
+}
+
+#[must_use]
+pub const fn encode_i16(input: i16) -> [char; 3] {
+    ascii_to_chars(encode_i16_bytes(input))
+}
+
+#[must_use]
+pub const fn encode_u16(input: u16) -> [char; 3] {
+    ascii_to_chars(encode_u16_bytes(input))
+}
+
+/// Encode an `i16` as 3 base64url ASCII bytes
+#[must_use]
+pub const fn encode_i16_bytes(input: i16) -> [u8; 3] {
+    encode_16(input.to_be_bytes(), &Alphabet::BASE64URL)
 }
 
+/// Encode a `u16` as 3 base64url ASCII bytes
 #[must_use]
-pub fn encode_i16(input: i16) -> [char; 3] {
-    encode_16(input.to_be_bytes())
+pub const fn encode_u16_bytes(input: u16) -> [u8; 3] {
+    encode_16(input.to_be_bytes(), &Alphabet::BASE64URL)
 }
 
+/// Encode an `i16` as 3 ASCII bytes of the given alphabet
 #[must_use]
-pub fn encode_u16(input: u16) -> [char; 3] {
-    encode_16(input.to_be_bytes())
+pub const fn encode_i16_with(input: i16, alphabet: &Alphabet) -> [u8; 3] {
+    encode_16(input.to_be_bytes(), alphabet)
 }
 
+/// Encode a `u16` as 3 ASCII bytes of the given alphabet
 #[must_use]
-fn encode_16(bytes: [u8; 2]) -> [char; 3] {
+pub const fn encode_u16_with(input: u16, alphabet: &Alphabet) -> [u8; 3] {
+    encode_16(input.to_be_bytes(), alphabet)
+}
+
+/// Encode an `i16` into the first 3 bytes of `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than 3 bytes.
+pub const fn encode_i16_into(input: i16, output: &mut [u8]) -> Result<(), Error> {
+    write_into(encode_i16_bytes(input), output)
+}
+
+/// Encode a `u16` into the first 3 bytes of `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than 3 bytes.
+pub const fn encode_u16_into(input: u16, output: &mut [u8]) -> Result<(), Error> {
+    write_into(encode_u16_bytes(input), output)
+}
+
+#[must_use]
+const fn encode_16(bytes: [u8; 2], alphabet: &Alphabet) -> [u8; 3] {
     let product = encode_partial_16([bytes[0], bytes[1]]);
 
-    product.map(|d| char::from(ALPHABET_BASE64URL_BYTES[usize::from(d)]))
+    encode_sextets(product, alphabet)
+}
+
+pub const fn decode_i128(input: [char; 22]) -> Result<i128, Error> {
+    let c = const_try!(ascii_from_chars(input));
+    decode_i128_bytes(&c)
 }
 
-pub fn decode_i128(input: [char; 22]) -> Result<i128, Error> {
-    let bytes = decode_128(input)?;
+pub const fn decode_u128(input: [char; 22]) -> Result<u128, Error> {
+    let c = const_try!(ascii_from_chars(input));
+    decode_u128_bytes(&c)
+}
+
+/// Decode exactly 22 base64url ASCII bytes as an `i128`
+pub const fn decode_i128_bytes(input: &[u8]) -> Result<i128, Error> {
+    decode_i128_with(input, &Alphabet::BASE64URL)
+}
+
+/// Decode exactly 22 base64url ASCII bytes as a `u128`
+pub const fn decode_u128_bytes(input: &[u8]) -> Result<u128, Error> {
+    decode_u128_with(input, &Alphabet::BASE64URL)
+}
+
+/// Decode exactly 22 ASCII bytes of the given alphabet as an `i128`
+pub const fn decode_i128_with(input: &[u8], alphabet: &Alphabet) -> Result<i128, Error> {
+    let c = const_try!(decode_bytes(input, alphabet));
+    let bytes = const_try!(decode_128(c, alphabet));
     Ok(i128::from_be_bytes(bytes))
 }
 
-pub fn decode_u128(input: [char; 22]) -> Result<u128, Error> {
-    let bytes = decode_128(input)?;
+/// Decode exactly 22 ASCII bytes of the given alphabet as a `u128`
+pub const fn decode_u128_with(input: &[u8], alphabet: &Alphabet) -> Result<u128, Error> {
+    let c = const_try!(decode_bytes(input, alphabet));
+    let bytes = const_try!(decode_128(c, alphabet));
     Ok(u128::from_be_bytes(bytes))
 }
 
 #[rustfmt::skip]
-fn decode_128(input: [char; 22]) -> Result<[u8; 16], Error> {
-    let mut c: [u8; 22] = [0; 22];
-
-    for i in 0..=21 {
-        c[i] = decode_char(input[i])?;
-    }
-
+const fn decode_128(c: [u8; 22], alphabet: &Alphabet) -> Result<[u8; 16], Error> {
     let p1 = decode_quantum([c[0], c[1], c[2], c[3]]);
     let p2 = decode_quantum([c[4], c[5], c[6], c[7]]);
     let p3 = decode_quantum([c[8], c[9], c[10], c[11]]);
     let p4 = decode_quantum([c[12], c[13], c[14], c[15]]);
     let p5 = decode_quantum([c[16], c[17], c[18], c[19]]);
-    let p6 = decode_partial_8([c[20], c[21]])?;
+    let p6 = match decode_partial_8([c[20], c[21]]) {
+        Some(p) => p,
+        None => return Err(out_of_bounds(&c, alphabet)),
+    };
 
     Ok([
         p1[0], p1[1], p1[2],
@@ -218,27 +467,48 @@ fn decode_128(input: [char; 22]) -> Result<[u8; 16], Error> {
     ])
 }
 
-pub fn decode_i64(input: [char; 11]) -> Result<i64, Error> {
-    let bytes = decode_64(input)?;
+pub const fn decode_i64(input: [char; 11]) -> Result<i64, Error> {
+    let c = const_try!(ascii_from_chars(input));
+    decode_i64_bytes(&c)
+}
+
+pub const fn decode_u64(input: [char; 11]) -> Result<u64, Error> {
+    let c = const_try!(ascii_from_chars(input));
+    decode_u64_bytes(&c)
+}
+
+/// Decode exactly 11 base64url ASCII bytes as an `i64`
+pub const fn decode_i64_bytes(input: &[u8]) -> Result<i64, Error> {
+    decode_i64_with(input, &Alphabet::BASE64URL)
+}
+
+/// Decode exactly 11 base64url ASCII bytes as a `u64`
+pub const fn decode_u64_bytes(input: &[u8]) -> Result<u64, Error> {
+    decode_u64_with(input, &Alphabet::BASE64URL)
+}
+
+/// Decode exactly 11 ASCII bytes of the given alphabet as an `i64`
+pub const fn decode_i64_with(input: &[u8], alphabet: &Alphabet) -> Result<i64, Error> {
+    let c = const_try!(decode_bytes(input, alphabet));
+    let bytes = const_try!(decode_64(c, alphabet));
     Ok(i64::from_be_bytes(bytes))
 }
 
-pub fn decode_u64(input: [char; 11]) -> Result<u64, Error> {
-    let bytes = decode_64(input)?;
+/// Decode exactly 11 ASCII bytes of the given alphabet as a `u64`
+pub const fn decode_u64_with(input: &[u8], alphabet: &Alphabet) -> Result<u64, Error> {
+    let c = const_try!(decode_bytes(input, alphabet));
+    let bytes = const_try!(decode_64(c, alphabet));
     Ok(u64::from_be_bytes(bytes))
 }
 
 #[rustfmt::skip]
-fn decode_64(input: [char; 11]) -> Result<[u8; 8], Error> {
-    let mut c: [u8; 11] = [0; 11];
-
-    for i in 0..=10 {
-        c[i] = decode_char(input[i])?;
-    }
-
+const fn decode_64(c: [u8; 11], alphabet: &Alphabet) -> Result<[u8; 8], Error> {
     let p1 = decode_quantum([c[0], c[1], c[2], c[3]]);
     let p2 = decode_quantum([c[4], c[5], c[6], c[7]]);
-    let p3 = decode_partial_16([c[8], c[9], c[10]])?;
+    let p3 = match decode_partial_16([c[8], c[9], c[10]]) {
+        Some(p) => p,
+        None => return Err(out_of_bounds(&c, alphabet)),
+    };
 
     Ok([
         p1[0], p1[1], p1[2],
@@ -247,26 +517,47 @@ fn decode_64(input: [char; 11]) -> Result<[u8; 8], Error> {
     ])
 }
 
-pub fn decode_i32(input: [char; 6]) -> Result<i32, Error> {
-    let bytes = decode_32(input)?;
+pub const fn decode_i32(input: [char; 6]) -> Result<i32, Error> {
+    let c = const_try!(ascii_from_chars(input));
+    decode_i32_bytes(&c)
+}
+
+pub const fn decode_u32(input: [char; 6]) -> Result<u32, Error> {
+    let c = const_try!(ascii_from_chars(input));
+    decode_u32_bytes(&c)
+}
+
+/// Decode exactly 6 base64url ASCII bytes as an `i32`
+pub const fn decode_i32_bytes(input: &[u8]) -> Result<i32, Error> {
+    decode_i32_with(input, &Alphabet::BASE64URL)
+}
+
+/// Decode exactly 6 base64url ASCII bytes as a `u32`
+pub const fn decode_u32_bytes(input: &[u8]) -> Result<u32, Error> {
+    decode_u32_with(input, &Alphabet::BASE64URL)
+}
+
+/// Decode exactly 6 ASCII bytes of the given alphabet as an `i32`
+pub const fn decode_i32_with(input: &[u8], alphabet: &Alphabet) -> Result<i32, Error> {
+    let c = const_try!(decode_bytes(input, alphabet));
+    let bytes = const_try!(decode_32(c, alphabet));
     Ok(i32::from_be_bytes(bytes))
 }
 
-pub fn decode_u32(input: [char; 6]) -> Result<u32, Error> {
-    let bytes = decode_32(input)?;
+/// Decode exactly 6 ASCII bytes of the given alphabet as a `u32`
+pub const fn decode_u32_with(input: &[u8], alphabet: &Alphabet) -> Result<u32, Error> {
+    let c = const_try!(decode_bytes(input, alphabet));
+    let bytes = const_try!(decode_32(c, alphabet));
     Ok(u32::from_be_bytes(bytes))
 }
 
 #[rustfmt::skip]
-fn decode_32(input: [char; 6]) -> Result<[u8; 4], Error> {
-    let mut c: [u8; 6] = [0; 6];
-
-    for i in 0..=5 {
-        c[i] = decode_char(input[i])?;
-    }
-
+const fn decode_32(c: [u8; 6], alphabet: &Alphabet) -> Result<[u8; 4], Error> {
     let p1 = decode_quantum([c[0], c[1], c[2], c[3]]);
-    let p2 = decode_partial_8([c[4], c[5]])?;
+    let p2 = match decode_partial_8([c[4], c[5]]) {
+        Some(p) => p,
+        None => return Err(out_of_bounds(&c, alphabet)),
+    };
 
     Ok([
         p1[0], p1[1], p1[2],
@@ -274,31 +565,240 @@ fn decode_32(input: [char; 6]) -> Result<[u8; 4], Error> {
     ])
 }
 
-pub fn decode_i16(input: [char; 3]) -> Result<i16, Error> {
-    let bytes = decode_16(input)?;
+pub const fn decode_i16(input: [char; 3]) -> Result<i16, Error> {
+    let c = const_try!(ascii_from_chars(input));
+    decode_i16_bytes(&c)
+}
+
+pub const fn decode_u16(input: [char; 3]) -> Result<u16, Error> {
+    let c = const_try!(ascii_from_chars(input));
+    decode_u16_bytes(&c)
+}
+
+/// Decode exactly 3 base64url ASCII bytes as an `i16`
+pub const fn decode_i16_bytes(input: &[u8]) -> Result<i16, Error> {
+    decode_i16_with(input, &Alphabet::BASE64URL)
+}
+
+/// Decode exactly 3 base64url ASCII bytes as a `u16`
+pub const fn decode_u16_bytes(input: &[u8]) -> Result<u16, Error> {
+    decode_u16_with(input, &Alphabet::BASE64URL)
+}
+
+/// Decode exactly 3 ASCII bytes of the given alphabet as an `i16`
+pub const fn decode_i16_with(input: &[u8], alphabet: &Alphabet) -> Result<i16, Error> {
+    let c = const_try!(decode_bytes(input, alphabet));
+    let bytes = const_try!(decode_16(c, alphabet));
     Ok(i16::from_be_bytes(bytes))
 }
 
-pub fn decode_u16(input: [char; 3]) -> Result<u16, Error> {
-    let bytes = decode_16(input)?;
+/// Decode exactly 3 ASCII bytes of the given alphabet as a `u16`
+pub const fn decode_u16_with(input: &[u8], alphabet: &Alphabet) -> Result<u16, Error> {
+    let c = const_try!(decode_bytes(input, alphabet));
+    let bytes = const_try!(decode_16(c, alphabet));
     Ok(u16::from_be_bytes(bytes))
 }
 
-fn decode_16(input: [char; 3]) -> Result<[u8; 2], Error> {
-    let mut c: [u8; 3] = [0; 3];
+const fn decode_16(c: [u8; 3], alphabet: &Alphabet) -> Result<[u8; 2], Error> {
+    match decode_partial_16(c) {
+        Some(p1) => Ok(p1),
+        None => Err(out_of_bounds(&c, alphabet)),
+    }
+}
+
+/// Encode an `i128` as 22 ASCII bytes which sort in the same order as the integer
+///
+/// The sign bit is flipped before encoding with [`Alphabet::SORTABLE`].
+#[must_use]
+pub const fn encode_i128_sortable(input: i128) -> [u8; 22] {
+    encode_128((input ^ i128::MIN).to_be_bytes(), &Alphabet::SORTABLE)
+}
+
+/// Encode a `u128` as 22 ASCII bytes which sort in the same order as the integer
+///
+/// This is equivalent to [`encode_u128_with`] using [`Alphabet::SORTABLE`].
+#[must_use]
+pub const fn encode_u128_sortable(input: u128) -> [u8; 22] {
+    encode_128(input.to_be_bytes(), &Alphabet::SORTABLE)
+}
+
+/// Decode exactly 22 ASCII bytes produced by [`encode_i128_sortable`]
+pub const fn decode_i128_sortable(input: &[u8]) -> Result<i128, Error> {
+    let output = const_try!(decode_i128_with(input, &Alphabet::SORTABLE));
+    Ok(output ^ i128::MIN)
+}
+
+/// Decode exactly 22 ASCII bytes produced by [`encode_u128_sortable`]
+pub const fn decode_u128_sortable(input: &[u8]) -> Result<u128, Error> {
+    decode_u128_with(input, &Alphabet::SORTABLE)
+}
+
+/// Encode an `i64` as 11 ASCII bytes which sort in the same order as the integer
+///
+/// The sign bit is flipped before encoding with [`Alphabet::SORTABLE`].
+#[must_use]
+pub const fn encode_i64_sortable(input: i64) -> [u8; 11] {
+    encode_64((input ^ i64::MIN).to_be_bytes(), &Alphabet::SORTABLE)
+}
+
+/// Encode a `u64` as 11 ASCII bytes which sort in the same order as the integer
+///
+/// This is equivalent to [`encode_u64_with`] using [`Alphabet::SORTABLE`].
+#[must_use]
+pub const fn encode_u64_sortable(input: u64) -> [u8; 11] {
+    encode_64(input.to_be_bytes(), &Alphabet::SORTABLE)
+}
+
+/// Decode exactly 11 ASCII bytes produced by [`encode_i64_sortable`]
+pub const fn decode_i64_sortable(input: &[u8]) -> Result<i64, Error> {
+    let output = const_try!(decode_i64_with(input, &Alphabet::SORTABLE));
+    Ok(output ^ i64::MIN)
+}
+
+/// Decode exactly 11 ASCII bytes produced by [`encode_u64_sortable`]
+pub const fn decode_u64_sortable(input: &[u8]) -> Result<u64, Error> {
+    decode_u64_with(input, &Alphabet::SORTABLE)
+}
+
+/// Encode an `i32` as 6 ASCII bytes which sort in the same order as the integer
+///
+/// The sign bit is flipped before encoding with [`Alphabet::SORTABLE`].
+#[must_use]
+pub const fn encode_i32_sortable(input: i32) -> [u8; 6] {
+    encode_32((input ^ i32::MIN).to_be_bytes(), &Alphabet::SORTABLE)
+}
+
+/// Encode a `u32` as 6 ASCII bytes which sort in the same order as the integer
+///
+/// This is equivalent to [`encode_u32_with`] using [`Alphabet::SORTABLE`].
+#[must_use]
+pub const fn encode_u32_sortable(input: u32) -> [u8; 6] {
+    encode_32(input.to_be_bytes(), &Alphabet::SORTABLE)
+}
+
+/// Decode exactly 6 ASCII bytes produced by [`encode_i32_sortable`]
+pub const fn decode_i32_sortable(input: &[u8]) -> Result<i32, Error> {
+    let output = const_try!(decode_i32_with(input, &Alphabet::SORTABLE));
+    Ok(output ^ i32::MIN)
+}
+
+/// Decode exactly 6 ASCII bytes produced by [`encode_u32_sortable`]
+pub const fn decode_u32_sortable(input: &[u8]) -> Result<u32, Error> {
+    decode_u32_with(input, &Alphabet::SORTABLE)
+}
+
+/// Encode an `i16` as 3 ASCII bytes which sort in the same order as the integer
+///
+/// The sign bit is flipped before encoding with [`Alphabet::SORTABLE`].
+#[must_use]
+pub const fn encode_i16_sortable(input: i16) -> [u8; 3] {
+    encode_16((input ^ i16::MIN).to_be_bytes(), &Alphabet::SORTABLE)
+}
+
+/// Encode a `u16` as 3 ASCII bytes which sort in the same order as the integer
+///
+/// This is equivalent to [`encode_u16_with`] using [`Alphabet::SORTABLE`].
+#[must_use]
+pub const fn encode_u16_sortable(input: u16) -> [u8; 3] {
+    encode_16(input.to_be_bytes(), &Alphabet::SORTABLE)
+}
+
+/// Decode exactly 3 ASCII bytes produced by [`encode_i16_sortable`]
+pub const fn decode_i16_sortable(input: &[u8]) -> Result<i16, Error> {
+    let output = const_try!(decode_i16_with(input, &Alphabet::SORTABLE));
+    Ok(output ^ i16::MIN)
+}
+
+/// Decode exactly 3 ASCII bytes produced by [`encode_u16_sortable`]
+pub const fn decode_u16_sortable(input: &[u8]) -> Result<u16, Error> {
+    decode_u16_with(input, &Alphabet::SORTABLE)
+}
 
-    for i in 0..=2 {
-        c[i] = decode_char(input[i])?;
+/// Number of characters needed to encode `bytes` bytes, without padding
+#[must_use]
+pub const fn encoded_len(bytes: usize) -> usize {
+    (bytes * 8).div_ceil(6)
+}
+
+/// Encode an array of `N` bytes as `M` ASCII bytes of the given alphabet
+///
+/// `M` must be equal to [`encoded_len(N)`](encoded_len), which is checked at compile time.
+/// Any unused bits of the last character are set to zero.
+#[must_use]
+pub const fn encode_array<const N: usize, const M: usize>(
+    input: &[u8; N],
+    alphabet: &Alphabet,
+) -> [u8; M] {
+    const { assert!(M == encoded_len(N), "M must be equal to encoded_len(N)") };
+
+    let mut output = [0; M];
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    let mut o = 0;
+
+    let mut i = 0;
+    while i < N {
+        buffer = (buffer << 8) | input[i] as u16;
+        bits += 8;
+
+        while bits >= 6 {
+            bits -= 6;
+            output[o] = alphabet.encode((buffer >> bits) as u8);
+            o += 1;
+        }
+
+        i += 1;
     }
 
-    let p1 = decode_partial_16(c)?;
+    if bits > 0 {
+        output[o] = alphabet.encode((buffer << (6 - bits)) as u8);
+    }
 
-    Ok(p1)
+    output
+}
+
+/// Decode exactly `M` ASCII bytes of the given alphabet as an array of `N` bytes
+///
+/// `M` must be equal to [`encoded_len(N)`](encoded_len), which is checked at compile time.
+/// Returns [`Error::OutOfBoundsCharacter`] if any unused bits of the last character are not zero.
+pub const fn decode_array<const N: usize, const M: usize>(
+    input: &[u8],
+    alphabet: &Alphabet,
+) -> Result<[u8; N], Error> {
+    const { assert!(M == encoded_len(N), "M must be equal to encoded_len(N)") };
+
+    let c: [u8; M] = const_try!(decode_bytes(input, alphabet));
+    let padding_bits = M * 6 - N * 8;
+
+    if padding_bits > 0 && c[M - 1] & ((1 << padding_bits) - 1) != 0 {
+        return Err(out_of_bounds(&c, alphabet));
+    }
+
+    let mut output = [0; N];
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    let mut o = 0;
+
+    let mut i = 0;
+    while o < N {
+        buffer = (buffer << 6) | c[i] as u16;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            output[o] = (buffer >> bits) as u8;
+            o += 1;
+        }
+
+        i += 1;
+    }
+
+    Ok(output)
 }
 
 #[must_use]
 #[rustfmt::skip]
-fn encode_quantum(input: [u8; 3]) -> [u8; 4] {
+const fn encode_quantum(input: [u8; 3]) -> [u8; 4] {
     let c1 = input[0] >> 2;
 
     let c2 = (
@@ -322,7 +822,7 @@ fn encode_quantum(input: [u8; 3]) -> [u8; 4] {
 
 #[must_use]
 #[rustfmt::skip]
-fn encode_partial_16(input: [u8; 2]) -> [u8; 3] {
+const fn encode_partial_16(input: [u8; 2]) -> [u8; 3] {
     let c1 = input[0] >> 2;
 
     let c2 = (
@@ -339,7 +839,7 @@ fn encode_partial_16(input: [u8; 2]) -> [u8; 3] {
 
 #[must_use]
 #[rustfmt::skip]
-fn encode_partial_8(input: u8) -> [u8; 2] {
+const fn encode_partial_8(input: u8) -> [u8; 2] {
     let c1 = input >> 2;
 
     let c2 = input << 4 & 0b0011_0000;
@@ -349,7 +849,7 @@ fn encode_partial_8(input: u8) -> [u8; 2] {
 
 #[must_use]
 #[rustfmt::skip]
-fn decode_quantum(input: [u8; 4]) -> [u8; 3] {
+const fn decode_quantum(input: [u8; 4]) -> [u8; 3] {
     let d1 = (
         input[0] << 2
     ) | (
@@ -375,9 +875,9 @@ fn decode_quantum(input: [u8; 4]) -> [u8; 3] {
 }
 
 #[rustfmt::skip]
-fn decode_partial_16(input: [u8; 3]) -> Result<[u8; 2], Error> {
+const fn decode_partial_16(input: [u8; 3]) -> Option<[u8; 2]> {
     if input[2] & 0b0000_0011 != 0 {
-        return Err(Error::OutOfBoundsCharacter);
+        return None;
     }
     
     let d1 = (
@@ -394,13 +894,13 @@ fn decode_partial_16(input: [u8; 3]) -> Result<[u8; 2], Error> {
         0b0000_1111
     );
 
-    Ok([d1, d2])
+    Some([d1, d2])
 }
 
 #[rustfmt::skip]
-fn decode_partial_8(input: [u8; 2]) -> Result<u8, Error> {
+const fn decode_partial_8(input: [u8; 2]) -> Option<u8> {
     if input[1] & 0b0000_1111 != 0 {
-        return Err(Error::OutOfBoundsCharacter);
+        return None;
     }
 
     let d1 = (
@@ -409,14 +909,14 @@ fn decode_partial_8(input: [u8; 2]) -> Result<u8, Error> {
         input[1] >> 4
     );
 
-    Ok(d1)
+    Some(d1)
 }
 
 #[cfg(test)]
 mod tests {
     extern crate std;
 
-    use crate::{base64, Error};
+    use crate::{base64, Alphabet, Error};
 
     const QUANTUM_BINARY: [[u8; 3]; 12] = [
         [0b00000100, 0b00010000, 0b01000001],
@@ -814,6 +1314,478 @@ mod tests {
         }
     }
 
+    #[test]
+    fn encode_i128_bytes_validation() {
+        for i in 0..=11 {
+            let output = base64::encode_i128_bytes(I128_INT[i]);
+            assert_eq!(output, BASE64_128_BIT[i].map(|c| c as u8));
+        }
+    }
+
+    #[test]
+    fn decode_i128_bytes_validation() {
+        for i in 0..=11 {
+            let input = BASE64_128_BIT[i].map(|c| c as u8);
+            let output = base64::decode_i128_bytes(&input).expect("failed to decode input");
+            assert_eq!(output, I128_INT[i]);
+        }
+    }
+
+    #[test]
+    fn encode_u128_bytes_validation() {
+        for i in 0..=11 {
+            let output = base64::encode_u128_bytes(U128_INT[i]);
+            assert_eq!(output, BASE64_128_BIT[i].map(|c| c as u8));
+        }
+    }
+
+    #[test]
+    fn decode_u128_bytes_validation() {
+        for i in 0..=11 {
+            let input = BASE64_128_BIT[i].map(|c| c as u8);
+            let output = base64::decode_u128_bytes(&input).expect("failed to decode input");
+            assert_eq!(output, U128_INT[i]);
+        }
+    }
+
+    #[test]
+    fn encode_i64_bytes_validation() {
+        for i in 0..=11 {
+            let output = base64::encode_i64_bytes(I64_INT[i]);
+            assert_eq!(output, BASE64_64_BIT[i].map(|c| c as u8));
+        }
+    }
+
+    #[test]
+    fn decode_i64_bytes_validation() {
+        for i in 0..=11 {
+            let input = BASE64_64_BIT[i].map(|c| c as u8);
+            let output = base64::decode_i64_bytes(&input).expect("failed to decode input");
+            assert_eq!(output, I64_INT[i]);
+        }
+    }
+
+    #[test]
+    fn encode_u64_bytes_validation() {
+        for i in 0..=11 {
+            let output = base64::encode_u64_bytes(U64_INT[i]);
+            assert_eq!(output, BASE64_64_BIT[i].map(|c| c as u8));
+        }
+    }
+
+    #[test]
+    fn decode_u64_bytes_validation() {
+        for i in 0..=11 {
+            let input = BASE64_64_BIT[i].map(|c| c as u8);
+            let output = base64::decode_u64_bytes(&input).expect("failed to decode input");
+            assert_eq!(output, U64_INT[i]);
+        }
+    }
+
+    #[test]
+    fn encode_i32_bytes_validation() {
+        for i in 0..=11 {
+            let output = base64::encode_i32_bytes(I32_INT[i]);
+            assert_eq!(output, BASE64_32_BIT[i].map(|c| c as u8));
+        }
+    }
+
+    #[test]
+    fn decode_i32_bytes_validation() {
+        for i in 0..=11 {
+            let input = BASE64_32_BIT[i].map(|c| c as u8);
+            let output = base64::decode_i32_bytes(&input).expect("failed to decode input");
+            assert_eq!(output, I32_INT[i]);
+        }
+    }
+
+    #[test]
+    fn encode_u32_bytes_validation() {
+        for i in 0..=11 {
+            let output = base64::encode_u32_bytes(U32_INT[i]);
+            assert_eq!(output, BASE64_32_BIT[i].map(|c| c as u8));
+        }
+    }
+
+    #[test]
+    fn decode_u32_bytes_validation() {
+        for i in 0..=11 {
+            let input = BASE64_32_BIT[i].map(|c| c as u8);
+            let output = base64::decode_u32_bytes(&input).expect("failed to decode input");
+            assert_eq!(output, U32_INT[i]);
+        }
+    }
+
+    #[test]
+    fn encode_i16_bytes_validation() {
+        for i in 0..=11 {
+            let output = base64::encode_i16_bytes(I16_INT[i]);
+            assert_eq!(output, BASE64_16_BIT[i].map(|c| c as u8));
+        }
+    }
+
+    #[test]
+    fn decode_i16_bytes_validation() {
+        for i in 0..=11 {
+            let input = BASE64_16_BIT[i].map(|c| c as u8);
+            let output = base64::decode_i16_bytes(&input).expect("failed to decode input");
+            assert_eq!(output, I16_INT[i]);
+        }
+    }
+
+    #[test]
+    fn encode_u16_bytes_validation() {
+        for i in 0..=11 {
+            let output = base64::encode_u16_bytes(U16_INT[i]);
+            assert_eq!(output, BASE64_16_BIT[i].map(|c| c as u8));
+        }
+    }
+
+    #[test]
+    fn decode_u16_bytes_validation() {
+        for i in 0..=11 {
+            let input = BASE64_16_BIT[i].map(|c| c as u8);
+            let output = base64::decode_u16_bytes(&input).expect("failed to decode input");
+            assert_eq!(output, U16_INT[i]);
+        }
+    }
+
+    #[test]
+    fn encode_with_standard_alphabet() {
+        for i in 0..=11 {
+            let output = base64::encode_i64_with(I64_INT[i], &Alphabet::STANDARD);
+            let expected = BASE64_64_BIT[i].map(|c| match c {
+                '-' => b'+',
+                '_' => b'/',
+                c => c as u8,
+            });
+
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn decode_with_standard_alphabet() {
+        for i in 0..=11 {
+            let input = BASE64_128_BIT[i].map(|c| match c {
+                '-' => b'+',
+                '_' => b'/',
+                c => c as u8,
+            });
+
+            let output = base64::decode_u128_with(&input, &Alphabet::STANDARD)
+                .expect("failed to decode input");
+            assert_eq!(output, U128_INT[i]);
+        }
+    }
+
+    /// Deterministic xorshift generator, so ordering tests need no external dependency
+    fn xorshift(state: &mut u128) -> u128 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    macro_rules! sortable_order_test {
+        ($test:ident, $int:ident, $encode:path, $decode:path) => {
+            #[test]
+            fn $test() {
+                let mut state = 0x2545_f491_4f6c_dd1d_9e37_79b9_7f4a_7c15u128;
+                let mut values: std::vec::Vec<$int> =
+                    std::vec![$int::MIN, $int::MIN + 1, 0, 1, $int::MAX - 1, $int::MAX];
+
+                for _ in 0..200 {
+                    let v = xorshift(&mut state);
+                    values.push($int::from_be_bytes(
+                        v.to_be_bytes()[..$int::BITS as usize / 8]
+                            .try_into()
+                            .unwrap(),
+                    ));
+                }
+
+                for a in &values {
+                    let encoded_a = $encode(*a);
+                    assert_eq!($decode(&encoded_a), Ok(*a));
+
+                    for b in &values {
+                        let encoded_b = $encode(*b);
+                        assert_eq!(a < b, encoded_a < encoded_b, "{a} {b}");
+                        assert_eq!(a.cmp(b), encoded_a.cmp(&encoded_b), "{a} {b}");
+                    }
+                }
+            }
+        };
+    }
+
+    sortable_order_test!(
+        sortable_i128_order,
+        i128,
+        base64::encode_i128_sortable,
+        base64::decode_i128_sortable
+    );
+    sortable_order_test!(
+        sortable_u128_order,
+        u128,
+        base64::encode_u128_sortable,
+        base64::decode_u128_sortable
+    );
+    sortable_order_test!(
+        sortable_i64_order,
+        i64,
+        base64::encode_i64_sortable,
+        base64::decode_i64_sortable
+    );
+    sortable_order_test!(
+        sortable_u64_order,
+        u64,
+        base64::encode_u64_sortable,
+        base64::decode_u64_sortable
+    );
+    sortable_order_test!(
+        sortable_i32_order,
+        i32,
+        base64::encode_i32_sortable,
+        base64::decode_i32_sortable
+    );
+    sortable_order_test!(
+        sortable_u32_order,
+        u32,
+        base64::encode_u32_sortable,
+        base64::decode_u32_sortable
+    );
+    sortable_order_test!(
+        sortable_i16_order,
+        i16,
+        base64::encode_i16_sortable,
+        base64::decode_i16_sortable
+    );
+    sortable_order_test!(
+        sortable_u16_order,
+        u16,
+        base64::encode_u16_sortable,
+        base64::decode_u16_sortable
+    );
+
+    #[test]
+    fn const_validation() {
+        const ENCODED: [u8; 11] = base64::encode_i64_bytes(6926187988806058650);
+        const DECODED: Result<i64, Error> = base64::decode_i64_bytes(&ENCODED);
+        const CHARS: [char; 6] = base64::encode_u32(2620502095);
+        const INVALID: Result<u16, Error> = base64::decode_u16_bytes(b"AA=");
+
+        assert_eq!(ENCODED, BASE64_64_BIT[2].map(|c| c as u8));
+        assert_eq!(DECODED, Ok(I64_INT[2]));
+        assert_eq!(CHARS, BASE64_32_BIT[2]);
+        assert_eq!(
+            INVALID,
+            Err(Error::InvalidCharacter {
+                index: 2,
+                character: '='
+            })
+        );
+    }
+
+    #[test]
+    fn encode_into_validation() {
+        let mut buffer = [0u8; 16];
+
+        base64::encode_i64_into(I64_INT[2], &mut buffer).expect("failed to encode input");
+        assert_eq!(buffer[..11], BASE64_64_BIT[2].map(|c| c as u8));
+        assert_eq!(buffer[11..], [0; 5]);
+    }
+
+    #[test]
+    fn encode_into_short_buffer() {
+        let mut buffer = [0u8; 10];
+
+        let output = base64::encode_u64_into(0, &mut buffer).expect_err("encode did not fail");
+        assert_eq!(
+            output,
+            Error::InvalidLength {
+                actual: 10,
+                expected: 11
+            }
+        );
+    }
+
+    #[test]
+    fn decode_bytes_invalid_length() {
+        for input in ["", "AAAAAAAAAA", "AAAAAAAAAAAA"] {
+            let output =
+                base64::decode_i64_bytes(input.as_bytes()).expect_err("decode did not fail");
+            assert_eq!(
+                output,
+                Error::InvalidLength {
+                    actual: input.len(),
+                    expected: 11
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn decode_bytes_non_ascii() {
+        let output =
+            base64::decode_i16_bytes("A\u{e9}".as_bytes()).expect_err("decode did not fail");
+        assert_eq!(
+            output,
+            Error::InvalidCharacter {
+                index: 1,
+                character: '\u{c3}'
+            }
+        );
+
+        let output = base64::decode_i16(['A', 'A', '\u{c1}']).expect_err("decode did not fail");
+        assert_eq!(
+            output,
+            Error::InvalidCharacter {
+                index: 2,
+                character: '\u{c1}'
+            }
+        );
+    }
+
+    #[test]
+    fn decode_out_of_bounds_details() {
+        let output = base64::decode_u64_bytes(b"AAAAAAAAAAB").expect_err("decode did not fail");
+        let Error::OutOfBoundsCharacter {
+            index,
+            character,
+            allowed,
+        } = output
+        else {
+            panic!("unexpected error {output:?}");
+        };
+
+        assert_eq!((index, character), (10, 'B'));
+        assert_eq!(allowed.as_str(), "AEIMQUYcgkosw048");
+
+        let output = base64::decode_i32_with(b"AAAAAB", &Alphabet::STANDARD)
+            .expect_err("decode did not fail");
+        let Error::OutOfBoundsCharacter { allowed, .. } = output else {
+            panic!("unexpected error {output:?}");
+        };
+
+        assert_eq!(allowed.as_str(), "AQgw");
+    }
+
+    #[test]
+    fn error_for_str() {
+        let input = "AA\u{e9}AAAAAAAA";
+
+        let output = base64::decode_i64_bytes(input.as_bytes()).expect_err("decode did not fail");
+        assert_eq!(
+            base64::error_for_str(output, input),
+            Error::InvalidCharacter {
+                index: 2,
+                character: '\u{e9}'
+            }
+        );
+
+        let input = "AA\u{e9}AAAAAAAAA";
+
+        let output = base64::decode_i64_bytes(input.as_bytes()).expect_err("decode did not fail");
+        assert_eq!(
+            base64::error_for_str(output, input),
+            Error::InvalidLength {
+                actual: 12,
+                expected: 11
+            }
+        );
+
+        let input = "AA\u{e9}AAAAAAA";
+
+        let output = base64::decode_i64_bytes(input.as_bytes()).expect_err("decode did not fail");
+        assert_eq!(
+            base64::error_for_str(output, input),
+            Error::InvalidCharacter {
+                index: 2,
+                character: '\u{e9}'
+            }
+        );
+    }
+
+    #[test]
+    fn array_matches_integers() {
+        for int in I64_INT {
+            let input = int.to_be_bytes();
+            let encoded: [u8; 11] = base64::encode_array(&input, &Alphabet::BASE64URL);
+
+            assert_eq!(encoded, base64::encode_i64_bytes(int));
+            assert_eq!(
+                base64::decode_array::<8, 11>(&encoded, &Alphabet::BASE64URL),
+                Ok(input)
+            );
+        }
+
+        let encoded: [u8; 22] = base64::encode_array(&u128::MAX.to_be_bytes(), &Alphabet::SORTABLE);
+        assert_eq!(encoded, base64::encode_u128_sortable(u128::MAX));
+
+        let encoded: [u8; 6] = base64::encode_array(&[0xDE, 0xAD, 0xBE, 0xEF], &Alphabet::STANDARD);
+        assert_eq!(
+            encoded,
+            base64::encode_u32_with(0xDEADBEEF, &Alphabet::STANDARD)
+        );
+
+        let encoded: [u8; 3] = base64::encode_array(&[0xFF, 0xFF], &Alphabet::BASE64URL);
+        assert_eq!(encoded, base64::encode_u16_bytes(u16::MAX));
+    }
+
+    #[test]
+    fn array_sha256_digest() {
+        // sha256 of an empty input
+        const DIGEST: [u8; 32] = [
+            0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8, 0x99, 0x6f,
+            0xb9, 0x24, 0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95, 0x99, 0x1b,
+            0x78, 0x52, 0xb8, 0x55,
+        ];
+        const ENCODED: &[u8; 43] = b"47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU";
+
+        assert_eq!(
+            &base64::encode_array::<32, 43>(&DIGEST, &Alphabet::BASE64URL),
+            ENCODED
+        );
+        assert_eq!(
+            base64::decode_array::<32, 43>(ENCODED, &Alphabet::BASE64URL),
+            Ok(DIGEST)
+        );
+    }
+
+    #[test]
+    fn array_without_padding_bits() {
+        let input = [0xFF; 12];
+        let encoded: [u8; 16] = base64::encode_array(&input, &Alphabet::BASE64URL);
+
+        assert_eq!(&encoded, b"________________");
+        assert_eq!(
+            base64::decode_array::<12, 16>(&encoded, &Alphabet::BASE64URL),
+            Ok(input)
+        );
+    }
+
+    #[test]
+    fn array_out_of_bounds() {
+        let output =
+            base64::decode_array::<20, 27>(b"AAAAAAAAAAAAAAAAAAAAAAAAAAB", &Alphabet::BASE64URL)
+                .expect_err("decode did not fail");
+        let Error::OutOfBoundsCharacter { index, allowed, .. } = output else {
+            panic!("unexpected error {output:?}");
+        };
+
+        assert_eq!(index, 26);
+        assert_eq!(allowed.as_str(), "AEIMQUYcgkosw048");
+
+        let output = base64::decode_array::<20, 27>(b"AAA", &Alphabet::BASE64URL);
+        assert_eq!(
+            output,
+            Err(Error::InvalidLength {
+                actual: 3,
+                expected: 27
+            })
+        );
+    }
+
     #[test]
     fn encode_quantum_validation() {
         for i in 0..=11 {
@@ -850,7 +1822,7 @@ mod tests {
     fn decode_partial_16_validation() {
         for i in 0..=11 {
             let output = base64::decode_partial_16(PARTIAL_16_BASE64[i])
-                .expect("decode_partial_16 returned an unexpected Err");
+                .expect("decode_partial_16 returned an unexpected None");
 
             assert_eq!(output, PARTIAL_16_BINARY[i]);
         }
@@ -860,13 +1832,13 @@ mod tests {
     fn decode_partial_8_validation() {
         for i in 0..=11 {
             let output = base64::decode_partial_8(PARTIAL_8_BASE64[i])
-                .expect("decode_partial_8 returned an unexpected Err");
+                .expect("decode_partial_8 returned an unexpected None");
 
             assert_eq!(output, PARTIAL_8_BINARY[i]);
         }
     }
 
-    /// decode_partial_16() should return an Error::OutOfBoundsCharacter
+    /// decode_partial_16() should return None
     /// when an out of bounds character is encountered
     ///
     /// Such characters include any character who's base64 index number, expressed as a u8, has it's first and/or second bit set to 1.
@@ -884,14 +1856,13 @@ mod tests {
     #[test]
     fn decode_partial_16_out_of_bounds_detection() {
         for i in PARTIAL_16_BASE64_OUTOFBOUNDS {
-            let output = base64::decode_partial_16([0, 0, i])
-                .expect_err("decode_partial_16 did not return an Err");
+            let output = base64::decode_partial_16([0, 0, i]);
 
-            assert_eq!(output, Error::OutOfBoundsCharacter);
+            assert_eq!(output, None);
         }
     }
 
-    /// decode_partial_8() should return an Error::OutOfBoundsCharacter
+    /// decode_partial_8() should return None
     /// when an out of bounds character is encountered
     ///
     /// Such characters include any character who's base64 index number, expressed as a u8, has any combination of it's first, second, third for forth bit set to 1.
@@ -909,10 +1880,9 @@ mod tests {
     #[test]
     fn decode_partial_8_out_of_bounds_detection() {
         for i in PARTIAL_8_BASE64_OUTOFBOUNDS {
-            let output = base64::decode_partial_8([0, i])
-                .expect_err("decode_partial_8 did not return an Err");
+            let output = base64::decode_partial_8([0, i]);
 
-            assert_eq!(output, Error::OutOfBoundsCharacter);
+            assert_eq!(output, None);
         }
     }
 }
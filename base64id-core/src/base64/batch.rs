@@ -0,0 +1,641 @@
+//! Batch encoding and decoding of integer slices
+//!
+//! 64 and 128 bit integers are translated 8 characters at a time using SWAR (SIMD within a register) arithmetic on `u64` values.
+//! Any input the SWAR path cannot handle, including all 32 and 16 bit integers, falls back to the scalar functions of the parent module.
+
+use super::{
+    decode_i128_bytes, decode_i16_bytes, decode_i32_bytes, decode_i64_bytes, decode_u128_bytes,
+    decode_u16_bytes, decode_u32_bytes, decode_u64_bytes, encode_i16_bytes, encode_i32_bytes,
+    encode_u16_bytes, encode_u32_bytes,
+};
+use crate::{Alphabet, Error, SliceError};
+
+/// Encode each `i128` of `input` as 22 base64url ASCII bytes, written consecutively into `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than `input.len() * 22` bytes.
+pub fn encode_slice_i128(input: &[i128], output: &mut [u8]) -> Result<(), Error> {
+    let output = output_chunks::<22>(input.len(), output)?;
+
+    for (v, o) in input.iter().zip(output) {
+        *o = encode_swar_128(u128::from_be_bytes(v.to_be_bytes()));
+    }
+
+    Ok(())
+}
+
+/// Encode each `u128` of `input` as 22 base64url ASCII bytes, written consecutively into `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than `input.len() * 22` bytes.
+pub fn encode_slice_u128(input: &[u128], output: &mut [u8]) -> Result<(), Error> {
+    let output = output_chunks::<22>(input.len(), output)?;
+
+    for (v, o) in input.iter().zip(output) {
+        *o = encode_swar_128(*v);
+    }
+
+    Ok(())
+}
+
+/// Decode consecutive 22 byte base64url ids from `input` into `output`
+///
+/// `input` must be exactly `output.len() * 22` bytes long.
+/// On failure, the returned [`SliceError`] contains the index of the first invalid id.
+pub fn decode_slice_i128(input: &[u8], output: &mut [i128]) -> Result<(), SliceError> {
+    let input = input_chunks::<22>(input, output.len())?;
+
+    for (index, (c, o)) in input.zip(output.iter_mut()).enumerate() {
+        *o = match decode_swar_128(c) {
+            Some(v) => i128::from_be_bytes(v.to_be_bytes()),
+            None => decode_i128_bytes(c).map_err(|error| SliceError { index, error })?,
+        };
+    }
+
+    Ok(())
+}
+
+/// Decode consecutive 22 byte base64url ids from `input` into `output`
+///
+/// `input` must be exactly `output.len() * 22` bytes long.
+/// On failure, the returned [`SliceError`] contains the index of the first invalid id.
+pub fn decode_slice_u128(input: &[u8], output: &mut [u128]) -> Result<(), SliceError> {
+    let input = input_chunks::<22>(input, output.len())?;
+
+    for (index, (c, o)) in input.zip(output.iter_mut()).enumerate() {
+        *o = match decode_swar_128(c) {
+            Some(v) => v,
+            None => decode_u128_bytes(c).map_err(|error| SliceError { index, error })?,
+        };
+    }
+
+    Ok(())
+}
+
+/// Encode each `i64` of `input` as 11 base64url ASCII bytes, written consecutively into `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than `input.len() * 11` bytes.
+pub fn encode_slice_i64(input: &[i64], output: &mut [u8]) -> Result<(), Error> {
+    let output = output_chunks::<11>(input.len(), output)?;
+
+    for (v, o) in input.iter().zip(output) {
+        *o = encode_swar_64(u64::from_be_bytes(v.to_be_bytes()));
+    }
+
+    Ok(())
+}
+
+/// Encode each `u64` of `input` as 11 base64url ASCII bytes, written consecutively into `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than `input.len() * 11` bytes.
+pub fn encode_slice_u64(input: &[u64], output: &mut [u8]) -> Result<(), Error> {
+    let output = output_chunks::<11>(input.len(), output)?;
+
+    for (v, o) in input.iter().zip(output) {
+        *o = encode_swar_64(*v);
+    }
+
+    Ok(())
+}
+
+/// Decode consecutive 11 byte base64url ids from `input` into `output`
+///
+/// `input` must be exactly `output.len() * 11` bytes long.
+/// On failure, the returned [`SliceError`] contains the index of the first invalid id.
+pub fn decode_slice_i64(input: &[u8], output: &mut [i64]) -> Result<(), SliceError> {
+    let input = input_chunks::<11>(input, output.len())?;
+
+    for (index, (c, o)) in input.zip(output.iter_mut()).enumerate() {
+        *o = match decode_swar_64(c) {
+            Some(v) => i64::from_be_bytes(v.to_be_bytes()),
+            None => decode_i64_bytes(c).map_err(|error| SliceError { index, error })?,
+        };
+    }
+
+    Ok(())
+}
+
+/// Decode consecutive 11 byte base64url ids from `input` into `output`
+///
+/// `input` must be exactly `output.len() * 11` bytes long.
+/// On failure, the returned [`SliceError`] contains the index of the first invalid id.
+pub fn decode_slice_u64(input: &[u8], output: &mut [u64]) -> Result<(), SliceError> {
+    let input = input_chunks::<11>(input, output.len())?;
+
+    for (index, (c, o)) in input.zip(output.iter_mut()).enumerate() {
+        *o = match decode_swar_64(c) {
+            Some(v) => v,
+            None => decode_u64_bytes(c).map_err(|error| SliceError { index, error })?,
+        };
+    }
+
+    Ok(())
+}
+
+/// Encode each `i32` of `input` as 6 base64url ASCII bytes, written consecutively into `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than `input.len() * 6` bytes.
+pub fn encode_slice_i32(input: &[i32], output: &mut [u8]) -> Result<(), Error> {
+    let output = output_chunks::<6>(input.len(), output)?;
+
+    for (v, o) in input.iter().zip(output) {
+        *o = encode_i32_bytes(*v);
+    }
+
+    Ok(())
+}
+
+/// Encode each `u32` of `input` as 6 base64url ASCII bytes, written consecutively into `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than `input.len() * 6` bytes.
+pub fn encode_slice_u32(input: &[u32], output: &mut [u8]) -> Result<(), Error> {
+    let output = output_chunks::<6>(input.len(), output)?;
+
+    for (v, o) in input.iter().zip(output) {
+        *o = encode_u32_bytes(*v);
+    }
+
+    Ok(())
+}
+
+/// Decode consecutive 6 byte base64url ids from `input` into `output`
+///
+/// `input` must be exactly `output.len() * 6` bytes long.
+/// On failure, the returned [`SliceError`] contains the index of the first invalid id.
+pub fn decode_slice_i32(input: &[u8], output: &mut [i32]) -> Result<(), SliceError> {
+    let input = input_chunks::<6>(input, output.len())?;
+
+    for (index, (c, o)) in input.zip(output.iter_mut()).enumerate() {
+        *o = decode_i32_bytes(c).map_err(|error| SliceError { index, error })?;
+    }
+
+    Ok(())
+}
+
+/// Decode consecutive 6 byte base64url ids from `input` into `output`
+///
+/// `input` must be exactly `output.len() * 6` bytes long.
+/// On failure, the returned [`SliceError`] contains the index of the first invalid id.
+pub fn decode_slice_u32(input: &[u8], output: &mut [u32]) -> Result<(), SliceError> {
+    let input = input_chunks::<6>(input, output.len())?;
+
+    for (index, (c, o)) in input.zip(output.iter_mut()).enumerate() {
+        *o = decode_u32_bytes(c).map_err(|error| SliceError { index, error })?;
+    }
+
+    Ok(())
+}
+
+/// Encode each `i16` of `input` as 3 base64url ASCII bytes, written consecutively into `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than `input.len() * 3` bytes.
+pub fn encode_slice_i16(input: &[i16], output: &mut [u8]) -> Result<(), Error> {
+    let output = output_chunks::<3>(input.len(), output)?;
+
+    for (v, o) in input.iter().zip(output) {
+        *o = encode_i16_bytes(*v);
+    }
+
+    Ok(())
+}
+
+/// Encode each `u16` of `input` as 3 base64url ASCII bytes, written consecutively into `output`
+///
+/// Returns [`Error::InvalidLength`] if `output` is shorter than `input.len() * 3` bytes.
+pub fn encode_slice_u16(input: &[u16], output: &mut [u8]) -> Result<(), Error> {
+    let output = output_chunks::<3>(input.len(), output)?;
+
+    for (v, o) in input.iter().zip(output) {
+        *o = encode_u16_bytes(*v);
+    }
+
+    Ok(())
+}
+
+/// Decode consecutive 3 byte base64url ids from `input` into `output`
+///
+/// `input` must be exactly `output.len() * 3` bytes long.
+/// On failure, the returned [`SliceError`] contains the index of the first invalid id.
+pub fn decode_slice_i16(input: &[u8], output: &mut [i16]) -> Result<(), SliceError> {
+    let input = input_chunks::<3>(input, output.len())?;
+
+    for (index, (c, o)) in input.zip(output.iter_mut()).enumerate() {
+        *o = decode_i16_bytes(c).map_err(|error| SliceError { index, error })?;
+    }
+
+    Ok(())
+}
+
+/// Decode consecutive 3 byte base64url ids from `input` into `output`
+///
+/// `input` must be exactly `output.len() * 3` bytes long.
+/// On failure, the returned [`SliceError`] contains the index of the first invalid id.
+pub fn decode_slice_u16(input: &[u8], output: &mut [u16]) -> Result<(), SliceError> {
+    let input = input_chunks::<3>(input, output.len())?;
+
+    for (index, (c, o)) in input.zip(output.iter_mut()).enumerate() {
+        *o = decode_u16_bytes(c).map_err(|error| SliceError { index, error })?;
+    }
+
+    Ok(())
+}
+
+fn output_chunks<const N: usize>(
+    len: usize,
+    output: &mut [u8],
+) -> Result<impl Iterator<Item = &mut [u8; N]>, Error> {
+    let expected = len * N;
+    let actual = output.len();
+
+    let mut output = match output.get_mut(..expected) {
+        Some(o) => o,
+        None => return Err(Error::InvalidLength { actual, expected }),
+    };
+
+    Ok(core::iter::from_fn(move || {
+        let (chunk, rest) = core::mem::take(&mut output).split_first_chunk_mut::<N>()?;
+        output = rest;
+        Some(chunk)
+    }))
+}
+
+fn input_chunks<const N: usize>(
+    mut input: &[u8],
+    len: usize,
+) -> Result<impl Iterator<Item = &[u8; N]>, SliceError> {
+    let chunks = input.len() / N;
+
+    if chunks != len || input.len() % N != 0 {
+        return Err(SliceError {
+            index: chunks.min(len),
+            error: Error::InvalidLength {
+                actual: input.len(),
+                expected: len * N,
+            },
+        });
+    }
+
+    Ok(core::iter::from_fn(move || {
+        let (chunk, rest) = input.split_first_chunk::<N>()?;
+        input = rest;
+        Some(chunk)
+    }))
+}
+
+fn encode_swar_128(input: u128) -> [u8; 22] {
+    let mut output = [0; 22];
+
+    output[..8].copy_from_slice(&swar::encode((input >> 80) as u64).to_be_bytes());
+    output[8..16]
+        .copy_from_slice(&swar::encode((input >> 32) as u64 & swar::BITS_48).to_be_bytes());
+    output[16..].copy_from_slice(&encode_tail::<6>((input as u64 & 0xFFFF_FFFF) << 4));
+
+    output
+}
+
+fn encode_swar_64(input: u64) -> [u8; 11] {
+    let mut output = [0; 11];
+
+    output[..8].copy_from_slice(&swar::encode(input >> 16).to_be_bytes());
+    output[8..].copy_from_slice(&encode_tail::<3>((input & 0xFFFF) << 2));
+
+    output
+}
+
+/// Returns `None` if `input` contains an invalid or out of bounds character
+fn decode_swar_128(input: &[u8; 22]) -> Option<u128> {
+    let (p1, rest) = input.split_first_chunk::<8>()?;
+    let (p2, tail) = rest.split_first_chunk::<8>()?;
+
+    let p1 = swar::decode(u64::from_be_bytes(*p1))?;
+    let p2 = swar::decode(u64::from_be_bytes(*p2))?;
+    let p3 = decode_tail(tail)?;
+
+    if p3 & 0b1111 != 0 {
+        return None;
+    }
+
+    Some((u128::from(p1) << 80) | (u128::from(p2) << 32) | u128::from(p3 >> 4))
+}
+
+/// Returns `None` if `input` contains an invalid or out of bounds character
+fn decode_swar_64(input: &[u8; 11]) -> Option<u64> {
+    let (head, tail) = input.split_first_chunk::<8>()?;
+
+    let p1 = swar::decode(u64::from_be_bytes(*head))?;
+    let p2 = decode_tail(tail)?;
+
+    if p2 & 0b11 != 0 {
+        return None;
+    }
+
+    Some((p1 << 16) | (p2 >> 2))
+}
+
+/// Encode the lowest `N * 6` bits of `bits` as `N` characters
+fn encode_tail<const N: usize>(bits: u64) -> [u8; N] {
+    let mut output = [0; N];
+
+    for (i, o) in output.iter_mut().enumerate() {
+        let sextet = (bits >> (6 * (N - 1 - i))) as u8;
+        *o = Alphabet::BASE64URL.encode(sextet);
+    }
+
+    output
+}
+
+fn decode_tail(input: &[u8]) -> Option<u64> {
+    input.iter().try_fold(0, |bits, &c| {
+        let sextet = Alphabet::BASE64URL.decode(c)?;
+        Some((bits << 6) | u64::from(sextet))
+    })
+}
+
+/// Base64url translation of 8 characters at a time, with each character held in one byte lane of a `u64`
+mod swar {
+    pub const BITS_48: u64 = 0xFFFF_FFFF_FFFF;
+
+    const ONES: u64 = 0x0101_0101_0101_0101;
+    const HIGH: u64 = 0x8080_8080_8080_8080;
+
+    /// Returns a lane mask of `0xFF` for each byte within `lo..=hi`
+    ///
+    /// Every byte of `x` must be less than `0x80`.
+    const fn in_range(x: u64, lo: u8, hi: u8) -> u64 {
+        let ge = x + ONES * (0x80 - lo as u64);
+        let gt = x + ONES * (0x7F - hi as u64);
+
+        ((ge & !gt & HIGH) >> 7) * 0xFF
+    }
+
+    /// Encode the lowest 48 bits of `bits` as 8 base64url characters
+    pub const fn encode(bits: u64) -> u64 {
+        let x = ((bits & 0xFFFF_FF00_0000) << 8) | (bits & 0x00FF_FFFF);
+        let x = ((x & 0x00FF_F000_00FF_F000) << 4) | (x & 0x0000_0FFF_0000_0FFF);
+        let x = ((x & 0x0FC0_0FC0_0FC0_0FC0) << 2) | (x & 0x003F_003F_003F_003F);
+
+        let upper = in_range(x, 0, 25);
+        let lower = in_range(x, 26, 51);
+        let digit = in_range(x, 52, 61);
+        let dash = in_range(x, 62, 62);
+        let underscore = in_range(x, 63, 63);
+
+        ((x & upper) + (upper & ONES) * 65)
+            | ((x & lower) + (lower & ONES) * 71)
+            | ((x & digit) - (digit & ONES) * 4)
+            | ((dash & ONES) * b'-' as u64)
+            | ((underscore & ONES) * b'_' as u64)
+    }
+
+    /// Decode 8 base64url characters as 48 bits
+    ///
+    /// Returns `None` if any character is not within the base64url alphabet.
+    pub const fn decode(x: u64) -> Option<u64> {
+        if x & HIGH != 0 {
+            return None;
+        }
+
+        let upper = in_range(x, b'A', b'Z');
+        let lower = in_range(x, b'a', b'z');
+        let digit = in_range(x, b'0', b'9');
+        let dash = in_range(x, b'-', b'-');
+        let underscore = in_range(x, b'_', b'_');
+
+        if upper | lower | digit | dash | underscore != u64::MAX {
+            return None;
+        }
+
+        let x = ((x & upper) - (upper & ONES) * 65)
+            | ((x & lower) - (lower & ONES) * 71)
+            | ((x & digit) + (digit & ONES) * 4)
+            | ((dash & ONES) * 62)
+            | ((underscore & ONES) * 63);
+
+        let x = ((x & 0x3F00_3F00_3F00_3F00) >> 2) | (x & 0x003F_003F_003F_003F);
+        let x = ((x & 0x0FFF_0000_0FFF_0000) >> 4) | (x & 0x0000_0FFF_0000_0FFF);
+        let x = ((x & 0x00FF_FFFF_0000_0000) >> 8) | (x & 0x0000_0000_00FF_FFFF);
+
+        Some(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+    use std::vec::Vec;
+
+    use crate::{base64, Error, SliceError};
+
+    /// Deterministic xorshift generator, so tests need no external dependency
+    fn random_values(count: usize) -> Vec<u128> {
+        let mut state = 0x2545_f491_4f6c_dd1d_9e37_79b9_7f4a_7c15u128;
+
+        (0..count)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state
+            })
+            .collect()
+    }
+
+    macro_rules! batch_roundtrip_test {
+        ($test:ident, $int:ident, $len:literal, $encode_slice:path, $decode_slice:path, $encode:path) => {
+            #[test]
+            fn $test() {
+                let mut input: Vec<$int> = vec![$int::MIN, $int::MAX, 0, 1];
+                input.extend(random_values(500).into_iter().map(|v| v as $int));
+
+                let mut encoded = vec![0u8; input.len() * $len];
+                $encode_slice(&input, &mut encoded).expect("failed to encode slice");
+
+                for (v, c) in input.iter().zip(encoded.chunks_exact($len)) {
+                    assert_eq!(c, $encode(*v));
+                }
+
+                let mut output = vec![0; input.len()];
+                $decode_slice(&encoded, &mut output).expect("failed to decode slice");
+
+                assert_eq!(input, output);
+            }
+        };
+    }
+
+    batch_roundtrip_test!(
+        batch_i128_roundtrip,
+        i128,
+        22,
+        base64::encode_slice_i128,
+        base64::decode_slice_i128,
+        base64::encode_i128_bytes
+    );
+    batch_roundtrip_test!(
+        batch_u128_roundtrip,
+        u128,
+        22,
+        base64::encode_slice_u128,
+        base64::decode_slice_u128,
+        base64::encode_u128_bytes
+    );
+    batch_roundtrip_test!(
+        batch_i64_roundtrip,
+        i64,
+        11,
+        base64::encode_slice_i64,
+        base64::decode_slice_i64,
+        base64::encode_i64_bytes
+    );
+    batch_roundtrip_test!(
+        batch_u64_roundtrip,
+        u64,
+        11,
+        base64::encode_slice_u64,
+        base64::decode_slice_u64,
+        base64::encode_u64_bytes
+    );
+    batch_roundtrip_test!(
+        batch_i32_roundtrip,
+        i32,
+        6,
+        base64::encode_slice_i32,
+        base64::decode_slice_i32,
+        base64::encode_i32_bytes
+    );
+    batch_roundtrip_test!(
+        batch_u32_roundtrip,
+        u32,
+        6,
+        base64::encode_slice_u32,
+        base64::decode_slice_u32,
+        base64::encode_u32_bytes
+    );
+    batch_roundtrip_test!(
+        batch_i16_roundtrip,
+        i16,
+        3,
+        base64::encode_slice_i16,
+        base64::decode_slice_i16,
+        base64::encode_i16_bytes
+    );
+    batch_roundtrip_test!(
+        batch_u16_roundtrip,
+        u16,
+        3,
+        base64::encode_slice_u16,
+        base64::decode_slice_u16,
+        base64::encode_u16_bytes
+    );
+
+    /// Every sextet value must be translated by the SWAR encoder exactly as the scalar encoder does
+    #[test]
+    fn swar_encode_all_sextets() {
+        for sextet in 0..64u64 {
+            let value = (0..11).fold(0u64, |v, _| (v << 6) | sextet);
+            let mut output = [0u8; 11];
+
+            base64::encode_slice_u64(&[value], &mut output).expect("failed to encode slice");
+            assert_eq!(output, base64::encode_u64_bytes(value));
+        }
+    }
+
+    /// Every byte value, at every SWAR lane, must be decoded exactly as the scalar decoder does
+    #[test]
+    fn swar_decode_all_bytes() {
+        for lane in 0..16 {
+            for byte in 0..=255u8 {
+                let mut input = *b"AAAAAAAAAAAAAAAAAAAAAA";
+                input[lane] = byte;
+
+                let mut output = [0u128];
+                let result = base64::decode_slice_u128(&input, &mut output);
+
+                match base64::decode_u128_bytes(&input) {
+                    Ok(v) => assert_eq!((result, output[0]), (Ok(()), v)),
+                    Err(error) => assert_eq!(result, Err(SliceError { index: 0, error })),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn decode_reports_first_invalid_index() {
+        let mut input = [b'A'; 55];
+        input[24] = b'=';
+        input[40] = b'=';
+
+        let mut output = [0i64; 5];
+
+        let err = base64::decode_slice_i64(&input, &mut output).expect_err("decode did not fail");
+        assert_eq!(
+            err,
+            SliceError {
+                index: 2,
+                error: Error::InvalidCharacter {
+                    index: 2,
+                    character: '='
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn decode_reports_out_of_bounds_index() {
+        let input = b"AAAAAAAAAAAAAAAAAAAAAB";
+        let mut output = [0u64; 2];
+
+        let err = base64::decode_slice_u64(input, &mut output).expect_err("decode did not fail");
+        assert_eq!(
+            err,
+            SliceError {
+                index: 1,
+                error: base64::decode_u64_bytes(b"AAAAAAAAAAB").unwrap_err()
+            }
+        );
+    }
+
+    #[test]
+    fn decode_invalid_length() {
+        let mut output = [0u32; 3];
+
+        let err = base64::decode_slice_u32(b"AAAAAAAAAAAAAAA", &mut output)
+            .expect_err("decode did not fail");
+        assert_eq!(
+            err,
+            SliceError {
+                index: 2,
+                error: Error::InvalidLength {
+                    actual: 15,
+                    expected: 18
+                }
+            }
+        );
+
+        let err = base64::decode_slice_u32(b"AAAAAAAAAAAAAAAAAAAAAAAA", &mut output)
+            .expect_err("decode did not fail");
+        assert_eq!(
+            err,
+            SliceError {
+                index: 3,
+                error: Error::InvalidLength {
+                    actual: 24,
+                    expected: 18
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn encode_short_output() {
+        let mut output = [0u8; 5];
+
+        let err = base64::encode_slice_i16(&[1, 2], &mut output).expect_err("encode did not fail");
+        assert_eq!(
+            err,
+            Error::InvalidLength {
+                actual: 5,
+                expected: 6
+            }
+        );
+    }
+}
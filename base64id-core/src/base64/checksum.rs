@@ -0,0 +1,190 @@
+//! Check characters for detecting typos within encoded ids
+//!
+//! The check character is calculated with the Luhn mod N algorithm, where N is 64.
+//! This detects every single character substitution, and most transpositions of adjacent characters.
+//! It is calculated from the 6 bit values of characters rather than the characters themselves, so works with any alphabet.
+
+use crate::{Alphabet, Error};
+
+/// Calculate the 6 bit value of the check character for `input`
+///
+/// Returns [`Error::InvalidCharacter`] if `input` contains a character that is not within `alphabet`.
+const fn luhn(input: &[u8], alphabet: &Alphabet) -> Result<u8, Error> {
+    let mut sum: u32 = 0;
+    let mut double = true;
+
+    let mut i = input.len();
+    while i > 0 {
+        i -= 1;
+
+        let sextet = match alphabet.decode(input[i]) {
+            Some(s) => s as u32,
+            None => {
+                return Err(Error::InvalidCharacter {
+                    index: i,
+                    character: input[i] as char,
+                })
+            }
+        };
+
+        let addend = match double {
+            true => sextet * 2,
+            false => sextet,
+        };
+
+        sum += (addend / 64) + (addend % 64);
+        double = !double;
+    }
+
+    Ok(((64 - sum % 64) % 64) as u8)
+}
+
+/// Append a check character to `N` encoded ASCII bytes of the given alphabet
+///
+/// `M` must be equal to `N + 1`, which is checked at compile time.
+///
+/// # Panics
+/// Panics if `input` contains a character that is not within `alphabet`.
+#[must_use]
+pub const fn append_check<const N: usize, const M: usize>(
+    input: [u8; N],
+    alphabet: &Alphabet,
+) -> [u8; M] {
+    const { assert!(M == N + 1, "M must be equal to N + 1") };
+
+    let check = match luhn(&input, alphabet) {
+        Ok(c) => alphabet.encode(c),
+        Err(_) => panic!("input contains characters outside of the alphabet"),
+    };
+
+    let mut output = [check; M];
+
+    let mut i = 0;
+    while i < N {
+        output[i] = input[i];
+        i += 1;
+    }
+
+    output
+}
+
+/// Verify the check character of exactly `N + 1` ASCII bytes of the given alphabet
+///
+/// Returns the `N` bytes preceding the check character, which may then be decoded.
+/// Returns [`Error::ChecksumMismatch`] if the check character does not match.
+pub const fn verify_check<'a, const N: usize>(
+    input: &'a [u8],
+    alphabet: &Alphabet,
+) -> Result<&'a [u8], Error> {
+    if input.len() != N + 1 {
+        return Err(Error::InvalidLength {
+            actual: input.len(),
+            expected: N + 1,
+        });
+    }
+
+    let (check, payload) = match input.split_last() {
+        Some(split) => split,
+        None => unreachable!(),
+    };
+
+    let expected = match luhn(payload, alphabet) {
+        Ok(c) => c,
+        Err(e) => return Err(e),
+    };
+
+    match alphabet.decode(*check) {
+        Some(c) if c == expected => Ok(payload),
+        Some(_) => Err(Error::ChecksumMismatch),
+        None => Err(Error::InvalidCharacter {
+            index: N,
+            character: *check as char,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{base64, Alphabet, Error};
+
+    const ENCODED: [u8; 11] = base64::encode_u64_bytes(3161873750843059683);
+
+    #[test]
+    fn append_verify_roundtrip() {
+        for alphabet in [Alphabet::BASE64URL, Alphabet::STANDARD, Alphabet::SORTABLE] {
+            let encoded = base64::encode_u64_with(3161873750843059683, &alphabet);
+            let checked: [u8; 12] = base64::append_check(encoded, &alphabet);
+
+            assert_eq!(checked[..11], encoded);
+            assert_eq!(
+                base64::verify_check::<11>(&checked, &alphabet),
+                Ok(&encoded[..])
+            );
+        }
+    }
+
+    #[test]
+    fn detects_all_substitutions() {
+        let checked: [u8; 12] = base64::append_check(ENCODED, &Alphabet::BASE64URL);
+
+        for i in 0..checked.len() {
+            for &symbol in Alphabet::BASE64URL.symbols() {
+                if symbol == checked[i] {
+                    continue;
+                }
+
+                let mut typo = checked;
+                typo[i] = symbol;
+
+                assert_eq!(
+                    base64::verify_check::<11>(&typo, &Alphabet::BASE64URL),
+                    Err(Error::ChecksumMismatch)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn detects_adjacent_transpositions() {
+        let checked: [u8; 12] = base64::append_check(ENCODED, &Alphabet::BASE64URL);
+
+        for i in 1..checked.len() {
+            if checked[i - 1] == checked[i] {
+                continue;
+            }
+
+            let mut typo = checked;
+            typo.swap(i - 1, i);
+
+            assert_eq!(
+                base64::verify_check::<11>(&typo, &Alphabet::BASE64URL),
+                Err(Error::ChecksumMismatch)
+            );
+        }
+    }
+
+    #[test]
+    fn verify_errors() {
+        assert_eq!(
+            base64::verify_check::<11>(&ENCODED, &Alphabet::BASE64URL),
+            Err(Error::InvalidLength {
+                actual: 11,
+                expected: 12
+            })
+        );
+        assert_eq!(
+            base64::verify_check::<2>(b"A=A", &Alphabet::BASE64URL),
+            Err(Error::InvalidCharacter {
+                index: 1,
+                character: '='
+            })
+        );
+        assert_eq!(
+            base64::verify_check::<2>(b"AA=", &Alphabet::BASE64URL),
+            Err(Error::InvalidCharacter {
+                index: 2,
+                character: '='
+            })
+        );
+    }
+}
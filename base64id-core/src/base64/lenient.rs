@@ -0,0 +1,298 @@
+//! Lenient decoding of input damaged during transport
+
+use super::error_for_str;
+use crate::{Alphabet, Error, Repairs};
+
+/// Decode `input` as exactly `N` characters, after applying any of the given `repairs` which are needed
+///
+/// Returns the decoded value along with the repairs which were applied.
+/// `decode` is any function which decodes exactly `N` ASCII bytes of `alphabet`:
+/// ```
+/// use base64id_core::{base64, Alphabet, Repairs};
+///
+/// let (id, applied) = base64::decode_lenient::<_, 11>(
+///     " \"K+E7bI+h7eM=\" ",
+///     Repairs::ALL,
+///     &Alphabet::BASE64URL,
+///     base64::decode_i64_bytes,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(id, 3161873750843059683);
+/// assert_eq!(applied, Repairs::ALL);
+/// ```
+///
+/// Repairs never alter characters within `alphabet`.
+/// [`Repairs::STRIP_PADDING`] is ignored if `=` is within `alphabet`, and [`Repairs::STANDARD_ALPHABET`]
+/// is ignored unless `alphabet` encodes the 6 bit values 62 and 63 as `-` and `_`, as base64url does.
+/// Neither `+` nor `/` is replaced if it is within `alphabet`.
+///
+/// The index of an invalid character within the returned [`Error`] refers to the original `input`.
+pub fn decode_lenient<T, const N: usize>(
+    input: &str,
+    repairs: Repairs,
+    alphabet: &Alphabet,
+    decode: impl FnOnce(&[u8]) -> Result<T, Error>,
+) -> Result<(T, Repairs), Error> {
+    decode_lenient_prefixed::<T, N>(input, "", repairs, alphabet, decode)
+}
+
+/// Decode `input` as exactly `N` characters starting with `prefix`, after applying any of the given `repairs` which are needed
+///
+/// As with [`decode_lenient`], except that [`Error::InvalidPrefix`] is returned if the repaired input does not start with `prefix`,
+/// and the characters of `prefix` are never altered. `N` includes the length of `prefix`,
+/// and `decode` is given the repaired input including `prefix`.
+pub fn decode_lenient_prefixed<T, const N: usize>(
+    input: &str,
+    prefix: &'static str,
+    repairs: Repairs,
+    alphabet: &Alphabet,
+    decode: impl FnOnce(&[u8]) -> Result<T, Error>,
+) -> Result<(T, Repairs), Error> {
+    let mut s = input;
+    let mut applied = Repairs::NONE;
+
+    if repairs.contains(Repairs::TRIM_WHITESPACE) {
+        repair(&mut s, &mut applied, Repairs::TRIM_WHITESPACE, str::trim);
+    }
+
+    if repairs.contains(Repairs::STRIP_QUOTES) {
+        repair(&mut s, &mut applied, Repairs::STRIP_QUOTES, strip_quotes);
+
+        if repairs.contains(Repairs::TRIM_WHITESPACE) {
+            repair(&mut s, &mut applied, Repairs::TRIM_WHITESPACE, str::trim);
+        }
+    }
+
+    if repairs.contains(Repairs::STRIP_PADDING) && alphabet.decode(b'=').is_none() {
+        repair(&mut s, &mut applied, Repairs::STRIP_PADDING, |s| {
+            s.trim_end_matches('=')
+        });
+    }
+
+    // s is always a subslice of input, so this is the byte offset of s within input
+    let offset = input[..s.as_ptr() as usize - input.as_ptr() as usize]
+        .chars()
+        .count();
+    let error = |e: Error| offset_error(error_for_str(e, s), offset);
+
+    if !s.starts_with(prefix) {
+        return Err(Error::InvalidPrefix { expected: prefix });
+    }
+
+    let mut bytes: [u8; N] = match s.as_bytes().try_into() {
+        Ok(b) => b,
+        Err(_) => {
+            return Err(error(Error::InvalidLength {
+                actual: s.len(),
+                expected: N,
+            }))
+        }
+    };
+
+    if repairs.contains(Repairs::STANDARD_ALPHABET)
+        && alphabet.encode(62) == b'-'
+        && alphabet.encode(63) == b'_'
+    {
+        for b in bytes[prefix.len()..].iter_mut() {
+            let replacement = match b {
+                b'+' if alphabet.decode(b'+').is_none() => b'-',
+                b'/' if alphabet.decode(b'/').is_none() => b'_',
+                _ => continue,
+            };
+
+            *b = replacement;
+            applied |= Repairs::STANDARD_ALPHABET;
+        }
+    }
+
+    match decode(&bytes) {
+        Ok(v) => Ok((v, applied)),
+        Err(e) => Err(error(e)),
+    }
+}
+
+fn repair<'a>(
+    s: &mut &'a str,
+    applied: &mut Repairs,
+    repair: Repairs,
+    f: impl FnOnce(&'a str) -> &'a str,
+) {
+    let repaired = f(s);
+
+    if repaired.len() != s.len() {
+        *s = repaired;
+        *applied |= repair;
+    }
+}
+
+fn strip_quotes(s: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|q| s.strip_prefix(q)?.strip_suffix(q))
+        .unwrap_or(s)
+}
+
+fn offset_error(error: Error, offset: usize) -> Error {
+    match error {
+        Error::InvalidCharacter { index, character } => Error::InvalidCharacter {
+            index: index + offset,
+            character,
+        },
+        Error::OutOfBoundsCharacter {
+            index,
+            character,
+            allowed,
+        } => Error::OutOfBoundsCharacter {
+            index: index + offset,
+            character,
+            allowed,
+        },
+        e => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{base64, Alphabet, Error, Repairs};
+
+    fn decode(input: &str, repairs: Repairs) -> Result<(u64, Repairs), Error> {
+        base64::decode_lenient::<_, 11>(
+            input,
+            repairs,
+            &Alphabet::BASE64URL,
+            base64::decode_u64_bytes,
+        )
+    }
+
+    #[test]
+    fn canonical_input_is_not_repaired() {
+        assert_eq!(decode("AAAAAAAAAAE", Repairs::ALL), Ok((1, Repairs::NONE)));
+        assert_eq!(decode("AAAAAAAAAAE", Repairs::NONE), Ok((1, Repairs::NONE)));
+    }
+
+    #[test]
+    fn each_repair() {
+        let cases = [
+            ("\t AAAAAAAAAAE\n", Repairs::TRIM_WHITESPACE),
+            ("\"AAAAAAAAAAE\"", Repairs::STRIP_QUOTES),
+            ("'AAAAAAAAAAE'", Repairs::STRIP_QUOTES),
+            ("AAAAAAAAAAE=", Repairs::STRIP_PADDING),
+            ("AAAAAAAAAAE==", Repairs::STRIP_PADDING),
+            ("/////////+E", Repairs::STANDARD_ALPHABET),
+        ];
+
+        for (input, repair) in cases {
+            let (_, applied) = decode(input, repair).expect("failed to repair input");
+            assert_eq!(applied, repair);
+
+            let (_, applied) = decode(input, Repairs::ALL).expect("failed to repair input");
+            assert_eq!(applied, repair);
+
+            decode(input, Repairs::ALL.union(Repairs::NONE))
+                .expect("failed to repair input with all repairs");
+        }
+    }
+
+    #[test]
+    fn disabled_repairs_are_not_applied() {
+        let input = " \"AAAAAAAAAAE=\" ";
+
+        assert_eq!(
+            decode(input, Repairs::ALL),
+            Ok((
+                1,
+                Repairs::TRIM_WHITESPACE | Repairs::STRIP_QUOTES | Repairs::STRIP_PADDING
+            ))
+        );
+        decode(input, Repairs::TRIM_WHITESPACE | Repairs::STRIP_QUOTES)
+            .expect_err("decoded input without stripping padding");
+        decode("+AAAAAAAAAE", Repairs::NONE).expect_err("decoded a standard character");
+    }
+
+    #[test]
+    fn mismatched_quotes_are_not_stripped() {
+        assert_eq!(
+            decode("\"AAAAAAAAAAE'", Repairs::ALL),
+            Err(Error::InvalidLength {
+                actual: 13,
+                expected: 11
+            })
+        );
+    }
+
+    #[test]
+    fn error_index_refers_to_input() {
+        assert_eq!(
+            decode("  \"AAAAAAAA\u{e9}AE\"", Repairs::ALL),
+            Err(Error::InvalidCharacter {
+                index: 11,
+                character: '\u{e9}'
+            })
+        );
+    }
+
+    #[test]
+    fn standard_alphabet_ignored_for_other_alphabets() {
+        let output = base64::decode_lenient::<_, 11>(
+            "+AAAAAAAAAA",
+            Repairs::ALL,
+            &Alphabet::SORTABLE,
+            base64::decode_u64_sortable,
+        );
+
+        assert_eq!(
+            output,
+            Err(Error::InvalidCharacter {
+                index: 0,
+                character: '+'
+            })
+        );
+    }
+
+    #[test]
+    fn standard_alphabet_keeps_characters_within_alphabet() {
+        const PLUS: Alphabet =
+            Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz012345678+-_");
+
+        let decode = |input| {
+            base64::decode_lenient::<_, 11>(input, Repairs::ALL, &PLUS, |input| {
+                base64::decode_u64_with(input, &PLUS)
+            })
+        };
+
+        assert_eq!(decode("+AAAAAAAAAA"), Ok((61 << 58, Repairs::NONE)));
+
+        let (_, applied) = decode("/////////+E").expect("failed to repair input");
+        assert_eq!(applied, Repairs::STANDARD_ALPHABET);
+    }
+
+    #[test]
+    fn prefix_is_checked_before_repairs() {
+        let decode = |input| {
+            base64::decode_lenient_prefixed::<_, 15>(
+                input,
+                "a+b_",
+                Repairs::ALL,
+                &Alphabet::BASE64URL,
+                |input| match base64::strip_prefix(input, "a+b_") {
+                    Ok(input) => base64::decode_u64_bytes(input),
+                    Err(e) => Err(e),
+                },
+            )
+        };
+
+        assert_eq!(
+            decode(" a+b_AAAAAAAAAAE "),
+            Ok((1, Repairs::TRIM_WHITESPACE))
+        );
+        assert_eq!(
+            decode("a+b_+AAAAAAAAAE").map(|(_, applied)| applied),
+            Ok(Repairs::STANDARD_ALPHABET)
+        );
+        assert_eq!(
+            decode("a-b_AAAAAAAAAAE"),
+            Err(Error::InvalidPrefix { expected: "a+b_" })
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Regular expressions matching encoded ids, for describing ids within schemas such as JSON Schema
+
+use core::fmt;
+
+use crate::{error::AllowedCharacters, Alphabet};
+
+/// A regular expression matching exactly the encoded ids of one length and alphabet
+///
+/// This displays as an anchored regular expression in the ECMA 262 dialect used by JSON Schema,
+/// such as `^[A-Za-z0-9\-_]{10}[AEIMQUYcgkosw048]$` for 64 bit integers.
+/// The last character is restricted to the characters which leave its padding bits unset, as described by [`AllowedCharacters`].
+#[derive(Debug, Clone, Copy)]
+pub struct Pattern<'a> {
+    alphabet: &'a Alphabet,
+    len: usize,
+    prefix: &'a str,
+    checksum: bool,
+}
+
+impl<'a> Pattern<'a> {
+    /// Match `len` characters of `alphabet`
+    ///
+    /// # Panics
+    /// Panics if `len` is zero.
+    #[must_use]
+    pub const fn new(alphabet: &'a Alphabet, len: usize) -> Self {
+        assert!(len > 0, "len must not be zero");
+
+        Self {
+            alphabet,
+            len,
+            prefix: "",
+            checksum: false,
+        }
+    }
+
+    /// Also match `prefix` before the encoded characters
+    #[must_use]
+    pub const fn with_prefix(self, prefix: &'a str) -> Self {
+        Self { prefix, ..self }
+    }
+
+    /// Also match a check character after the encoded characters
+    #[must_use]
+    pub const fn with_checksum(self) -> Self {
+        Self {
+            checksum: true,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for Pattern<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("^")?;
+
+        for c in self.prefix.chars() {
+            if "^$\\.*+?()[]{}|/".contains(c) {
+                f.write_str("\\")?;
+            }
+            write!(f, "{c}")?;
+        }
+
+        let symbols = self.alphabet.symbols();
+
+        if self.len > 1 {
+            write_class(f, symbols)?;
+            write!(f, "{{{}}}", self.len - 1)?;
+        }
+
+        match (self.len as u32 * 6) % 8 {
+            0 => write_class(f, symbols)?,
+            padding_bits => {
+                let allowed = AllowedCharacters::new(self.alphabet, padding_bits);
+                write_class(f, allowed.as_str().as_bytes())?;
+            }
+        }
+
+        if self.checksum {
+            write_class(f, symbols)?;
+        }
+
+        f.write_str("$")
+    }
+}
+
+/// Write a character class matching any of `symbols`, abbreviating runs of consecutive characters as ranges
+fn write_class(f: &mut fmt::Formatter<'_>, symbols: &[u8]) -> fmt::Result {
+    f.write_str("[")?;
+
+    let mut i = 0;
+    while i < symbols.len() {
+        let mut end = i;
+        while end + 1 < symbols.len() && symbols[end + 1] == symbols[end].wrapping_add(1) {
+            end += 1;
+        }
+
+        match end - i {
+            0 => write_class_char(f, symbols[i])?,
+            1 => {
+                write_class_char(f, symbols[i])?;
+                write_class_char(f, symbols[end])?;
+            }
+            _ => {
+                write_class_char(f, symbols[i])?;
+                f.write_str("-")?;
+                write_class_char(f, symbols[end])?;
+            }
+        }
+
+        i = end + 1;
+    }
+
+    f.write_str("]")
+}
+
+/// Write one character within a character class, escaping characters with a special meaning
+fn write_class_char(f: &mut fmt::Formatter<'_>, symbol: u8) -> fmt::Result {
+    if b"\\]^-[".contains(&symbol) {
+        f.write_str("\\")?;
+    }
+
+    write!(f, "{}", symbol as char)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use crate::{base64::Pattern, Alphabet};
+
+    #[test]
+    fn base64url() {
+        let expected = [
+            (3, r"^[A-Za-z0-9\-_]{2}[AEIMQUYcgkosw048]$"),
+            (6, r"^[A-Za-z0-9\-_]{5}[AQgw]$"),
+            (11, r"^[A-Za-z0-9\-_]{10}[AEIMQUYcgkosw048]$"),
+            (22, r"^[A-Za-z0-9\-_]{21}[AQgw]$"),
+            (4, r"^[A-Za-z0-9\-_]{3}[A-Za-z0-9\-_]$"),
+        ];
+
+        for (len, pattern) in expected {
+            assert_eq!(Pattern::new(&Alphabet::BASE64URL, len).to_string(), pattern);
+        }
+    }
+
+    #[test]
+    fn other_alphabets() {
+        assert_eq!(
+            Pattern::new(&Alphabet::STANDARD, 6).to_string(),
+            r"^[A-Za-z0-9+/]{5}[AQgw]$"
+        );
+        assert_eq!(
+            Pattern::new(&Alphabet::SORTABLE, 6).to_string(),
+            r"^[\-0-9A-Z_a-z]{5}[\-FVk]$"
+        );
+    }
+
+    #[test]
+    fn prefix_and_checksum() {
+        let pattern = Pattern::new(&Alphabet::BASE64URL, 6)
+            .with_prefix("usr.")
+            .with_checksum();
+
+        assert_eq!(
+            pattern.to_string(),
+            r"^usr\.[A-Za-z0-9\-_]{5}[AQgw][A-Za-z0-9\-_]$"
+        );
+    }
+}
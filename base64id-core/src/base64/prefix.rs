@@ -0,0 +1,142 @@
+//! Prefixes preceding encoded ids, such as `usr_AAAAAAAAAAE`
+
+use crate::Error;
+
+/// Prepend the `P` bytes of `prefix` to `N` encoded ASCII bytes
+///
+/// `M` must be equal to `P + N`, which is checked at compile time.
+#[must_use]
+pub const fn prepend_prefix<const P: usize, const N: usize, const M: usize>(
+    input: [u8; N],
+    prefix: &[u8; P],
+) -> [u8; M] {
+    const { assert!(M == P + N, "M must be equal to P + N") };
+
+    let mut output = [0; M];
+
+    let mut i = 0;
+    while i < P {
+        output[i] = prefix[i];
+        i += 1;
+    }
+
+    let mut i = 0;
+    while i < N {
+        output[P + i] = input[i];
+        i += 1;
+    }
+
+    output
+}
+
+/// Strip `prefix` from the start of `input`
+///
+/// Returns [`Error::InvalidPrefix`] if `input` does not start with `prefix`.
+pub const fn strip_prefix<'a>(input: &'a [u8], prefix: &'static str) -> Result<&'a [u8], Error> {
+    match input.split_at_checked(prefix.len()) {
+        Some((start, rest)) if bytes_eq(start, prefix.as_bytes()) => Ok(rest),
+        _ => Err(Error::InvalidPrefix { expected: prefix }),
+    }
+}
+
+/// Adjust an [`Error`] from decoding the input following `prefix`, so that it refers to the whole input
+#[must_use]
+pub const fn prefix_error(error: Error, prefix: &str) -> Error {
+    let offset = prefix.len();
+
+    match error {
+        Error::InvalidLength { actual, expected } => Error::InvalidLength {
+            actual: actual + offset,
+            expected: expected + offset,
+        },
+        Error::InvalidCharacter { index, character } => Error::InvalidCharacter {
+            index: index + offset,
+            character,
+        },
+        Error::OutOfBoundsCharacter {
+            index,
+            character,
+            allowed,
+        } => Error::OutOfBoundsCharacter {
+            index: index + offset,
+            character,
+            allowed,
+        },
+        e => e,
+    }
+}
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{base64, Error};
+
+    #[test]
+    fn prepend_strip_roundtrip() {
+        let prefixed: [u8; 15] = base64::prepend_prefix(*b"AAAAAAAAAAE", b"usr_");
+
+        assert_eq!(&prefixed, b"usr_AAAAAAAAAAE");
+        assert_eq!(
+            base64::strip_prefix(&prefixed, "usr_"),
+            Ok(&b"AAAAAAAAAAE"[..])
+        );
+    }
+
+    #[test]
+    fn invalid_prefix() {
+        for input in [
+            &b"AAAAAAAAAAE"[..],
+            b"ord_AAAAAAAAAAE",
+            b"usr",
+            b"USR_AAAAAAAAAAE",
+        ] {
+            assert_eq!(
+                base64::strip_prefix(input, "usr_"),
+                Err(Error::InvalidPrefix { expected: "usr_" })
+            );
+        }
+    }
+
+    #[test]
+    fn decode_errors_refer_to_whole_input() {
+        let decode = |input: &[u8]| match base64::strip_prefix(input, "usr_") {
+            Ok(rest) => base64::decode_u64_bytes(rest).map_err(|e| base64::prefix_error(e, "usr_")),
+            Err(e) => Err(e),
+        };
+
+        assert_eq!(decode(b"usr_AAAAAAAAAAE"), Ok(1));
+        assert_eq!(
+            decode(b"usr_AAAAAAAAAA"),
+            Err(Error::InvalidLength {
+                actual: 14,
+                expected: 15
+            })
+        );
+        assert_eq!(
+            decode(b"usr_AAAAA.AAAAE"),
+            Err(Error::InvalidCharacter {
+                index: 9,
+                character: '.'
+            })
+        );
+        assert!(matches!(
+            decode(b"usr_AAAAAAAAAAB"),
+            Err(Error::OutOfBoundsCharacter { index: 14, .. })
+        ));
+    }
+}
@@ -1,7 +1,9 @@
 use core::fmt;
 
+use crate::Alphabet;
+
 /// Enum for base64url decoding errors
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Error {
     /// Returned when input data contains an invalid number of characters
@@ -11,9 +13,26 @@ pub enum Error {
     /// - `i64` or `u64`: 11 characters
     /// - `i32` or `u32`: 6 characters
     /// - `i16` or `u16`: 3 characters
-    InvalidLength,
-    /// Returned when input data conatins a character that is not within the base64url alphabet
-    InvalidCharacter,
+    /// - `[u8; N]`: `(4 * N + 2) / 3` characters, such as 43 characters for `[u8; 32]`
+    ///
+    /// Types with a check character expect one more character, and types with a prefix also expect the length of the prefix,
+    /// so an `i64` id with the prefix `usr_` and a check character expects 16 characters.
+    InvalidLength {
+        /// Number of characters within the input data
+        actual: usize,
+        /// Number of characters the input data was expected to contain
+        expected: usize,
+    },
+    /// Returned when input data contains a character that is not within the alphabet
+    ///
+    /// When decoding bytes, a non-ASCII `character` is the offending byte interpreted as a Latin-1 character.
+    /// Decoding a `str` always reports the full offending character.
+    InvalidCharacter {
+        /// Index of the first invalid character
+        index: usize,
+        /// The first invalid character
+        character: char,
+    },
     /// Returned when the last character of input data is out of bounds
     ///
     /// For `i64`, `u64`, `i16` and `u16` values, the last character must be one of the following:
@@ -25,7 +44,31 @@ pub enum Error {
     /// ```txt
     /// AQgw
     /// ```
-    OutOfBoundsCharacter,
+    ///
+    /// These are the characters of the base64url alphabet, other alphabets allow their own equivalent characters.
+    OutOfBoundsCharacter {
+        /// Index of the last character
+        index: usize,
+        /// The last character
+        character: char,
+        /// The characters which are allowed in place of `character`
+        allowed: AllowedCharacters,
+    },
+    /// Returned when input data decodes to zero, but the value must be non-zero
+    ///
+    /// This is only returned for types containing a `NonZero` integer.
+    ZeroValue,
+    /// Returned when the check character of input data does not match the characters preceding it
+    ///
+    /// This is only returned for types with a check character, and usually means the id contains a typo.
+    ChecksumMismatch,
+    /// Returned when input data does not start with the prefix of the id type, such as `usr_`
+    ///
+    /// This is only returned for types with a prefix.
+    InvalidPrefix {
+        /// The prefix which input data was expected to start with
+        expected: &'static str,
+    },
 }
 
 impl core::error::Error for Error {}
@@ -33,14 +76,107 @@ impl core::error::Error for Error {}
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::InvalidLength => write!(f, "invalid length. number of characters was invalid"),
-            Error::InvalidCharacter => write!(
+            Error::InvalidLength { actual, expected } => write!(
+                f,
+                "invalid length. expected exactly {expected} characters but found {actual}"
+            ),
+            Error::InvalidCharacter { index, character } => write!(
+                f,
+                "invalid character {character:?} at index {index}. character is not within the alphabet"
+            ),
+            Error::OutOfBoundsCharacter {
+                index,
+                character,
+                allowed,
+            } => write!(
                 f,
-                "invalid character(s). expected only base64url characters"
+                "invalid character {character:?} at index {index}. last character was out of bounds, expected one of the following: {allowed}"
             ),
-            Error::OutOfBoundsCharacter => {
-                write!(f, "invalid character. last character was out of bounds")
-            }
+            Error::ZeroValue => write!(f, "invalid value. value must not be zero"),
+            Error::ChecksumMismatch => write!(
+                f,
+                "invalid check character. one or more characters are incorrect"
+            ),
+            Error::InvalidPrefix { expected } => write!(
+                f,
+                "invalid prefix. expected id to start with {expected:?}"
+            ),
+        }
+    }
+}
+
+/// The characters allowed as the last character of an encoded id
+///
+/// The last character of an encoded id contains unused padding bits, which must all be zero.
+/// This is the subset of an alphabet which leaves these bits unset.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct AllowedCharacters {
+    symbols: [u8; 16],
+    len: u8,
+}
+
+impl AllowedCharacters {
+    /// Create the set of characters within `alphabet` that leave the lower `padding_bits` of a sextet unset
+    pub(crate) const fn new(alphabet: &Alphabet, padding_bits: u32) -> Self {
+        let step = 1 << padding_bits;
+        let mut allowed = Self {
+            symbols: [0; 16],
+            len: 0,
+        };
+
+        let mut sextet = 0;
+        while sextet < 64 && (allowed.len as usize) < allowed.symbols.len() {
+            allowed.symbols[allowed.len as usize] = alphabet.encode(sextet);
+            allowed.len += 1;
+            sextet += step;
         }
+
+        allowed
+    }
+
+    /// Get the allowed characters as a `str`
+    #[must_use]
+    pub const fn as_str(&self) -> &str {
+        let (symbols, _) = self.symbols.split_at(self.len as usize);
+
+        match core::str::from_utf8(symbols) {
+            Ok(s) => s,
+            Err(_) => panic!("alphabet contains non-ASCII characters"),
+        }
+    }
+}
+
+impl fmt::Display for AllowedCharacters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for AllowedCharacters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AllowedCharacters")
+            .field(&self.as_str())
+            .finish()
+    }
+}
+
+/// Error returned when decoding a slice of ids fails
+#[derive(Debug, PartialEq, Eq)]
+pub struct SliceError {
+    /// Index of the first id which could not be decoded
+    pub index: usize,
+    /// The reason the id could not be decoded
+    pub error: Error,
+}
+
+impl core::error::Error for SliceError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for SliceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid id at index {}. {}", self.index, self.error)
     }
 }
@@ -5,8 +5,25 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+mod alphabet;
 #[allow(missing_docs)]
 pub mod base64;
 mod error;
+mod obfuscate;
+mod repairs;
+mod snowflake;
+mod string;
+mod ulid;
 
-pub use error::Error;
+pub use alphabet::Alphabet;
+pub use error::{AllowedCharacters, Error, SliceError};
+pub use obfuscate::Key;
+pub use repairs::Repairs;
+#[cfg(feature = "std")]
+pub use snowflake::SystemClock;
+pub use snowflake::{Clock, Snowflake, SnowflakeGenerator, SnowflakeParts};
+pub use string::Base64Str;
+pub use ulid::{Rng, UlidGenerator, UlidParts};
@@ -0,0 +1,269 @@
+use core::fmt;
+
+/// Number of Feistel rounds applied by a [`Key`]
+const ROUNDS: usize = 8;
+
+/// A key for obfuscating integers before they are encoded
+///
+/// Sequential integers, such as database primary keys, encode to sequential strings which leak how many ids exist.
+/// A `Key` applies a keyed permutation to an integer of any supported width, so that sequential integers map to unrelated integers of the same type.
+/// The permutation is a balanced Feistel network over the exact bit width of the integer, so every integer maps to exactly one other integer and back:
+/// ```
+/// use base64id_core::Key;
+///
+/// const KEY: Key = Key::new(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+///
+/// let obfuscated = KEY.obfuscate_u64(1);
+///
+/// assert_ne!(obfuscated, 1);
+/// assert_eq!(KEY.deobfuscate_u64(obfuscated), 1);
+/// ```
+///
+/// This hides the order and density of ids from casual observation, but is not encryption.
+/// Ids must still be authorized when used.
+#[derive(Clone)]
+pub struct Key {
+    round_keys: [u64; ROUNDS],
+}
+
+impl Key {
+    /// Create a new key from 128 bits of secret key material
+    #[must_use]
+    pub const fn new(key: u128) -> Key {
+        let mut round_keys = [0; ROUNDS];
+
+        // Each half seeds its own stream, so that every bit of the key affects every round key
+        let mut high = (key >> 64) as u64;
+        let mut low = key as u64;
+
+        let mut i = 0;
+        while i < ROUNDS {
+            high = high.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            low = low.wrapping_add(0xd1b5_4a32_d192_ed03);
+            round_keys[i] = mix(high) ^ mix(low).rotate_left(32);
+            i += 1;
+        }
+
+        Key { round_keys }
+    }
+
+    /// Create a new key from 16 bytes of secret key material
+    #[must_use]
+    pub const fn from_bytes(key: [u8; 16]) -> Key {
+        Key::new(u128::from_be_bytes(key))
+    }
+
+    /// Encrypt the lower `bits` of `input`
+    const fn encrypt(&self, input: u128, bits: u32) -> u128 {
+        let half = bits / 2;
+        let mask = (1 << half) - 1;
+
+        let mut left = (input >> half) & mask;
+        let mut right = input & mask;
+
+        let mut i = 0;
+        while i < ROUNDS {
+            let next = left ^ (self.round(right, i) & mask);
+            left = right;
+            right = next;
+            i += 1;
+        }
+
+        (left << half) | right
+    }
+
+    /// Reverse [`Key::encrypt`]
+    const fn decrypt(&self, input: u128, bits: u32) -> u128 {
+        let half = bits / 2;
+        let mask = (1 << half) - 1;
+
+        let mut left = (input >> half) & mask;
+        let mut right = input & mask;
+
+        let mut i = ROUNDS;
+        while i > 0 {
+            i -= 1;
+            let previous = right ^ (self.round(left, i) & mask);
+            right = left;
+            left = previous;
+        }
+
+        (left << half) | right
+    }
+
+    /// Feistel round function, for halves of at most 64 bits
+    const fn round(&self, half: u128, round: usize) -> u128 {
+        mix(half as u64 ^ self.round_keys[round]) as u128
+    }
+}
+
+/// Generate the `obfuscate_*` and `deobfuscate_*` methods of [`Key`] for each integer type
+macro_rules! key_methods {
+    ($($int:ident, $uint:ident, $obfuscate:ident, $deobfuscate:ident);* $(;)?) => {
+        impl Key {
+            $(
+                #[doc = concat!("Obfuscate an `", stringify!($int), "`, which is reversed by [`Key::", stringify!($deobfuscate), "`]")]
+                #[must_use]
+                pub const fn $obfuscate(&self, input: $int) -> $int {
+                    self.encrypt(input as $uint as u128, $int::BITS) as $uint as $int
+                }
+
+                #[doc = concat!("Reverse [`Key::", stringify!($obfuscate), "`]")]
+                #[must_use]
+                pub const fn $deobfuscate(&self, input: $int) -> $int {
+                    self.decrypt(input as $uint as u128, $int::BITS) as $uint as $int
+                }
+            )*
+        }
+    };
+}
+
+key_methods! {
+    i128, u128, obfuscate_i128, deobfuscate_i128;
+    u128, u128, obfuscate_u128, deobfuscate_u128;
+    i64, u64, obfuscate_i64, deobfuscate_i64;
+    u64, u64, obfuscate_u64, deobfuscate_u64;
+    i32, u32, obfuscate_i32, deobfuscate_i32;
+    u32, u32, obfuscate_u32, deobfuscate_u32;
+    i16, u16, obfuscate_i16, deobfuscate_i16;
+    u16, u16, obfuscate_u16, deobfuscate_u16;
+}
+
+// Key material is never printed
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Key").finish_non_exhaustive()
+    }
+}
+
+/// splitmix64 finalizer
+const fn mix(input: u64) -> u64 {
+    let mut z = input;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{collections::HashSet, format};
+
+    use crate::Key;
+
+    const KEY: Key = Key::new(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210);
+
+    fn xorshift(state: &mut u128) -> u128 {
+        *state ^= *state << 23;
+        *state ^= *state >> 17;
+        *state ^= *state << 26;
+        *state
+    }
+
+    macro_rules! obfuscate_test {
+        ($test_name:ident, $int:ident, $obfuscate:ident, $deobfuscate:ident) => {
+            #[test]
+            fn $test_name() {
+                let mut state = 0x2545_f491_4f6c_dd1d_2545_f491_4f6c_dd1d;
+                let inputs = HashSet::from([$int::MIN, $int::MAX, 0, 1, 2, 3]);
+                let outputs: HashSet<$int> = inputs.iter().map(|v| KEY.$obfuscate(*v)).collect();
+
+                assert_eq!(inputs.len(), outputs.len());
+
+                for value in inputs {
+                    assert_eq!(KEY.$deobfuscate(KEY.$obfuscate(value)), value);
+                }
+
+                for _ in 0..10_000 {
+                    let value = xorshift(&mut state) as $int;
+                    let obfuscated = KEY.$obfuscate(value);
+
+                    assert_eq!(KEY.$deobfuscate(obfuscated), value);
+                    assert_eq!(KEY.$obfuscate(KEY.$deobfuscate(value)), value);
+                }
+            }
+        };
+    }
+
+    obfuscate_test!(obfuscate_i128, i128, obfuscate_i128, deobfuscate_i128);
+    obfuscate_test!(obfuscate_u128, u128, obfuscate_u128, deobfuscate_u128);
+    obfuscate_test!(obfuscate_i64, i64, obfuscate_i64, deobfuscate_i64);
+    obfuscate_test!(obfuscate_u64, u64, obfuscate_u64, deobfuscate_u64);
+    obfuscate_test!(obfuscate_i32, i32, obfuscate_i32, deobfuscate_i32);
+    obfuscate_test!(obfuscate_u32, u32, obfuscate_u32, deobfuscate_u32);
+    obfuscate_test!(obfuscate_i16, i16, obfuscate_i16, deobfuscate_i16);
+    obfuscate_test!(obfuscate_u16, u16, obfuscate_u16, deobfuscate_u16);
+
+    #[test]
+    fn bijective_16_bit() {
+        let mut seen = [false; 1 << 16];
+
+        for value in 0..=u16::MAX {
+            let obfuscated = KEY.obfuscate_u16(value);
+            assert!(!seen[obfuscated as usize]);
+            seen[obfuscated as usize] = true;
+
+            assert_eq!(
+                KEY.obfuscate_i16(value as i16) as u16,
+                KEY.obfuscate_u16(value)
+            );
+        }
+    }
+
+    #[test]
+    fn sequential_values_are_not_sequential() {
+        let a = KEY.obfuscate_u64(1);
+        let b = KEY.obfuscate_u64(2);
+
+        assert_ne!(a, 1);
+        assert!(a.abs_diff(b) > 1 << 32);
+    }
+
+    /// Obfuscated ids are stored by clients, so the permutation must never change
+    #[test]
+    fn known_answers() {
+        assert_eq!(KEY.obfuscate_u64(1), 0xd68d_c912_ae83_824a);
+        assert_eq!(KEY.obfuscate_u16(1), 0x4ff7);
+        assert_eq!(KEY.obfuscate_i32(-1), 0x4c6e_3f4e);
+        assert_eq!(
+            KEY.obfuscate_u128(1),
+            0x1a19_5707_1229_0550_8540_e3c5_4079_6ce2
+        );
+    }
+
+    #[test]
+    fn high_half_changes_permutation() {
+        let other = Key::new(0x0123_4567_89ab_cdee_fedc_ba98_7654_3210);
+
+        assert_ne!(KEY.obfuscate_u64(1), other.obfuscate_u64(1));
+        assert_ne!(KEY.obfuscate_u128(1), other.obfuscate_u128(1));
+        assert_ne!(KEY.obfuscate_u16(1), other.obfuscate_u16(1));
+    }
+
+    #[test]
+    fn low_half_changes_permutation() {
+        let other = Key::new(0x0123_4567_89ab_cdef_fedc_ba98_7654_3211);
+
+        assert_ne!(KEY.obfuscate_u64(1), other.obfuscate_u64(1));
+        assert_ne!(KEY.obfuscate_u128(1), other.obfuscate_u128(1));
+        assert_ne!(KEY.obfuscate_u16(1), other.obfuscate_u16(1));
+    }
+
+    #[test]
+    fn keys_differ() {
+        let other = Key::new(0x0123_4567_89ab_cdef_fedc_ba98_7654_3211);
+
+        assert_ne!(KEY.obfuscate_u64(1), other.obfuscate_u64(1));
+        assert_eq!(
+            Key::from_bytes(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128.to_be_bytes())
+                .obfuscate_u64(1),
+            KEY.obfuscate_u64(1)
+        );
+    }
+
+    #[test]
+    fn debug_hides_key() {
+        assert_eq!(format!("{KEY:?}"), "Key { .. }");
+    }
+}
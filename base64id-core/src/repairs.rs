@@ -0,0 +1,136 @@
+use core::{fmt, ops};
+
+/// A set of repairs applied to damaged input by lenient decoding
+///
+/// Repairs are combined with `|`:
+/// ```
+/// use base64id_core::Repairs;
+///
+/// let repairs = Repairs::TRIM_WHITESPACE | Repairs::STRIP_QUOTES;
+///
+/// assert!(repairs.contains(Repairs::STRIP_QUOTES));
+/// assert!(!repairs.contains(Repairs::STRIP_PADDING));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Repairs(u8);
+
+impl Repairs {
+    /// No repairs
+    pub const NONE: Repairs = Repairs(0);
+
+    /// Remove leading and trailing whitespace
+    pub const TRIM_WHITESPACE: Repairs = Repairs(1);
+
+    /// Remove a pair of matching single or double quotes surrounding the input
+    pub const STRIP_QUOTES: Repairs = Repairs(1 << 1);
+
+    /// Remove trailing `=` padding characters
+    pub const STRIP_PADDING: Repairs = Repairs(1 << 2);
+
+    /// Replace the standard base64 characters `+` and `/` with the base64url characters `-` and `_`
+    pub const STANDARD_ALPHABET: Repairs = Repairs(1 << 3);
+
+    /// All repairs
+    pub const ALL: Repairs = Repairs(0b1111);
+
+    const NAMES: [(Repairs, &'static str); 4] = [
+        (Repairs::TRIM_WHITESPACE, "TRIM_WHITESPACE"),
+        (Repairs::STRIP_QUOTES, "STRIP_QUOTES"),
+        (Repairs::STRIP_PADDING, "STRIP_PADDING"),
+        (Repairs::STANDARD_ALPHABET, "STANDARD_ALPHABET"),
+    ];
+
+    /// Returns `true` if all repairs within `other` are also within `self`
+    #[must_use]
+    pub const fn contains(self, other: Repairs) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if no repairs are within `self`
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Combine the repairs of `self` and `other`
+    #[must_use]
+    pub const fn union(self, other: Repairs) -> Repairs {
+        Repairs(self.0 | other.0)
+    }
+}
+
+impl ops::BitOr for Repairs {
+    type Output = Repairs;
+
+    fn bitor(self, rhs: Repairs) -> Repairs {
+        self.union(rhs)
+    }
+}
+
+impl ops::BitOrAssign for Repairs {
+    fn bitor_assign(&mut self, rhs: Repairs) {
+        *self = self.union(rhs);
+    }
+}
+
+impl fmt::Debug for Repairs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Repairs(")?;
+
+        let mut names = Repairs::NAMES
+            .iter()
+            .filter(|(r, _)| self.contains(*r))
+            .map(|(_, name)| name);
+
+        match names.next() {
+            Some(name) => f.write_str(name)?,
+            None => f.write_str("NONE")?,
+        }
+
+        for name in names {
+            write!(f, " | {name}")?;
+        }
+
+        f.write_str(")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::format;
+
+    use crate::Repairs;
+
+    #[test]
+    fn union_contains() {
+        let mut repairs = Repairs::NONE;
+        assert!(repairs.is_empty());
+
+        repairs |= Repairs::STRIP_PADDING;
+        assert!(repairs.contains(Repairs::STRIP_PADDING));
+        assert!(!repairs.contains(Repairs::TRIM_WHITESPACE));
+        assert!(repairs.contains(Repairs::NONE));
+
+        assert_eq!(
+            Repairs::TRIM_WHITESPACE
+                | Repairs::STRIP_QUOTES
+                | Repairs::STRIP_PADDING
+                | Repairs::STANDARD_ALPHABET,
+            Repairs::ALL
+        );
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", Repairs::NONE), "Repairs(NONE)");
+        assert_eq!(
+            format!(
+                "{:?}",
+                Repairs::TRIM_WHITESPACE | Repairs::STANDARD_ALPHABET
+            ),
+            "Repairs(TRIM_WHITESPACE | STANDARD_ALPHABET)"
+        );
+    }
+}
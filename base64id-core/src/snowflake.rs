@@ -0,0 +1,396 @@
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// A source of the current time, in milliseconds since the UNIX epoch
+///
+/// This is implemented for any `Fn() -> u64`, so tests may supply a fixed or manually advanced clock.
+/// `SystemClock` reads the system time, and requires the `std` feature.
+pub trait Clock {
+    /// Milliseconds since the UNIX epoch
+    fn now_millis(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn now_millis(&self) -> u64 {
+        self()
+    }
+}
+
+/// A [`Clock`] reading the system time
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time is before the UNIX epoch");
+
+        now.as_millis() as u64
+    }
+}
+
+/// The layout of time ordered 64 bit ids, as popularised by Twitter's Snowflake
+///
+/// From most to least significant bit, an id contains:
+/// - an unused sign bit, so that ids are positive as an `i64`
+/// - milliseconds since `epoch`
+/// - the id of the worker which generated the id, within `worker_bits`
+/// - a sequence number distinguishing ids generated within the same millisecond, within `sequence_bits`
+///
+/// Workers generate ids without coordinating with each other, and ids sort in the order they were generated:
+/// ```
+/// use base64id_core::Snowflake;
+///
+/// // 2020-01-01T00:00:00Z
+/// const SNOWFLAKE: Snowflake = Snowflake::new(1_577_836_800_000, 10, 12);
+///
+/// let generator = SNOWFLAKE.generator(7, || 1_700_000_000_000);
+/// let id: u64 = generator.generate();
+/// let parts = SNOWFLAKE.decompose(id);
+///
+/// assert_eq!(parts.timestamp, 1_700_000_000_000);
+/// assert_eq!(parts.worker, 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snowflake {
+    epoch: u64,
+    worker_bits: u32,
+    sequence_bits: u32,
+}
+
+impl Snowflake {
+    /// Create a new layout, where `epoch` is in milliseconds since the UNIX epoch
+    ///
+    /// # Panics
+    /// Panics if `worker_bits + sequence_bits` is greater than 22.
+    /// This leaves at least 41 bits for the timestamp, which lasts for 69 years after `epoch`.
+    #[must_use]
+    pub const fn new(epoch: u64, worker_bits: u32, sequence_bits: u32) -> Snowflake {
+        assert!(
+            worker_bits <= 22 && sequence_bits <= 22 - worker_bits,
+            "worker_bits + sequence_bits must not be greater than 22"
+        );
+
+        Snowflake {
+            epoch,
+            worker_bits,
+            sequence_bits,
+        }
+    }
+
+    /// Milliseconds since the UNIX epoch, from which timestamps are counted
+    #[must_use]
+    pub const fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Number of bits storing the worker id
+    #[must_use]
+    pub const fn worker_bits(&self) -> u32 {
+        self.worker_bits
+    }
+
+    /// Number of bits storing the sequence number
+    #[must_use]
+    pub const fn sequence_bits(&self) -> u32 {
+        self.sequence_bits
+    }
+
+    /// Number of bits storing the timestamp
+    #[must_use]
+    pub const fn timestamp_bits(&self) -> u32 {
+        63 - self.worker_bits - self.sequence_bits
+    }
+
+    /// Extract the timestamp, worker and sequence number from an id
+    ///
+    /// This accepts any 64 bit integer, including ids of a type deriving [`Base64Id`](https://docs.rs/base64id/latest/base64id/derive.Base64Id.html).
+    #[must_use]
+    pub fn decompose(&self, id: impl Into<u64>) -> SnowflakeParts {
+        let id = id.into();
+
+        SnowflakeParts {
+            timestamp: self.epoch + (id >> (self.worker_bits + self.sequence_bits)),
+            worker: (id >> self.sequence_bits) & mask(self.worker_bits),
+            sequence: id & mask(self.sequence_bits),
+        }
+    }
+
+    /// Create a generator of ids for the given `worker`
+    ///
+    /// # Panics
+    /// Panics if `worker` does not fit within `worker_bits`.
+    #[must_use]
+    pub fn generator<C: Clock>(self, worker: u64, clock: C) -> SnowflakeGenerator<C> {
+        SnowflakeGenerator::new(self, worker, clock)
+    }
+}
+
+/// The timestamp, worker and sequence number of an id, as returned by [`Snowflake::decompose`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SnowflakeParts {
+    /// Milliseconds since the UNIX epoch
+    pub timestamp: u64,
+    /// The id of the worker which generated the id
+    pub worker: u64,
+    /// The sequence number of the id within its millisecond
+    pub sequence: u64,
+}
+
+/// Generates time ordered ids with the layout of a [`Snowflake`]
+///
+/// Generating ids is lock free, so a single generator may be shared between threads.
+/// Ids from one generator always increase, even when the clock moves backwards.
+/// When the clock moves backwards, or the sequence numbers of a millisecond are exhausted,
+/// ids continue from the last timestamp used rather than waiting for the clock to catch up.
+pub struct SnowflakeGenerator<C> {
+    config: Snowflake,
+    worker: u64,
+    clock: C,
+    /// The last timestamp used and its sequence number, packed as `timestamp << sequence_bits | sequence`
+    ///
+    /// This is `UNUSED` until the first id is generated.
+    state: AtomicU64,
+}
+
+/// The state of a generator which has not generated any ids
+///
+/// Packed states use at most 63 bits, so this never occurs once an id is generated.
+const UNUSED: u64 = u64::MAX;
+
+impl<C: Clock> SnowflakeGenerator<C> {
+    /// Create a generator of ids for the given `worker`
+    ///
+    /// # Panics
+    /// Panics if `worker` does not fit within the `worker_bits` of `config`.
+    #[must_use]
+    pub fn new(config: Snowflake, worker: u64, clock: C) -> SnowflakeGenerator<C> {
+        assert!(
+            worker <= mask(config.worker_bits),
+            "worker does not fit within worker_bits"
+        );
+
+        SnowflakeGenerator {
+            config,
+            worker,
+            clock,
+            state: AtomicU64::new(UNUSED),
+        }
+    }
+
+    /// Generate the next id
+    ///
+    /// Ids may be converted into any type with a `From<u64>` impl, including `i64` and `u64` types deriving [`Base64Id`](https://docs.rs/base64id/latest/base64id/derive.Base64Id.html).
+    ///
+    /// # Panics
+    /// Panics if the clock is before the epoch, or the timestamp no longer fits within its bits.
+    pub fn generate<T: From<u64>>(&self) -> T {
+        let sequence_bits = self.config.sequence_bits;
+        let timestamp_mask = mask(self.config.timestamp_bits());
+
+        let now = self
+            .clock
+            .now_millis()
+            .checked_sub(self.config.epoch)
+            .expect("clock is before the snowflake epoch");
+        assert!(
+            now <= timestamp_mask,
+            "timestamp does not fit within timestamp_bits"
+        );
+
+        let mut last = self.state.load(Ordering::Relaxed);
+        let next = loop {
+            // incrementing the packed state carries an exhausted sequence into the timestamp
+            let next = match last == UNUSED || now > last >> sequence_bits {
+                true => now << sequence_bits,
+                false => last + 1,
+            };
+
+            match self
+                .state
+                .compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => break next,
+                Err(actual) => last = actual,
+            }
+        };
+
+        let timestamp = next >> sequence_bits;
+        assert!(
+            timestamp <= timestamp_mask,
+            "timestamp does not fit within timestamp_bits"
+        );
+
+        let id = (timestamp << (self.config.worker_bits + sequence_bits))
+            | (self.worker << sequence_bits)
+            | (next & mask(sequence_bits));
+
+        T::from(id)
+    }
+
+    /// The layout of generated ids
+    #[must_use]
+    pub fn config(&self) -> &Snowflake {
+        &self.config
+    }
+
+    /// The worker id within generated ids
+    #[must_use]
+    pub fn worker(&self) -> u64 {
+        self.worker
+    }
+}
+
+impl<C> fmt::Debug for SnowflakeGenerator<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnowflakeGenerator")
+            .field("config", &self.config)
+            .field("worker", &self.worker)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A mask of the lowest `bits` bits
+const fn mask(bits: u32) -> u64 {
+    (1 << bits) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::cell::Cell;
+    use std::{collections::HashSet, thread, vec::Vec};
+
+    use crate::{Snowflake, SnowflakeParts};
+
+    const EPOCH: u64 = 1_577_836_800_000;
+    const SNOWFLAKE: Snowflake = Snowflake::new(EPOCH, 10, 12);
+
+    #[test]
+    fn layout() {
+        let generator = SNOWFLAKE.generator(5, || EPOCH + 3);
+        let id: u64 = generator.generate();
+
+        assert_eq!(id, (3 << 22) | (5 << 12));
+        assert_eq!(SNOWFLAKE.timestamp_bits(), 41);
+        assert_eq!(
+            SNOWFLAKE.decompose(id),
+            SnowflakeParts {
+                timestamp: EPOCH + 3,
+                worker: 5,
+                sequence: 0
+            }
+        );
+    }
+
+    #[test]
+    fn first_id_at_epoch() {
+        let generator = SNOWFLAKE.generator(5, || EPOCH);
+
+        assert_eq!(
+            SNOWFLAKE.decompose(generator.generate::<u64>()),
+            SnowflakeParts {
+                timestamp: EPOCH,
+                worker: 5,
+                sequence: 0
+            }
+        );
+        assert_eq!(SNOWFLAKE.decompose(generator.generate::<u64>()).sequence, 1);
+    }
+
+    #[test]
+    fn sequence_within_millisecond() {
+        let generator = SNOWFLAKE.generator(1, || EPOCH + 10);
+
+        for sequence in 0..100 {
+            let parts = SNOWFLAKE.decompose(generator.generate::<u64>());
+
+            assert_eq!(parts.timestamp, EPOCH + 10);
+            assert_eq!(parts.sequence, sequence);
+        }
+    }
+
+    #[test]
+    fn exhausted_sequence_borrows_next_millisecond() {
+        let snowflake = Snowflake::new(EPOCH, 0, 2);
+        let generator = snowflake.generator(0, || EPOCH + 10);
+
+        let parts: Vec<(u64, u64)> = (0..6)
+            .map(|_| snowflake.decompose(generator.generate::<u64>()))
+            .map(|p| (p.timestamp - EPOCH, p.sequence))
+            .collect();
+
+        assert_eq!(
+            parts,
+            [(10, 0), (10, 1), (10, 2), (10, 3), (11, 0), (11, 1)]
+        );
+    }
+
+    #[test]
+    fn monotonic_when_clock_moves_backwards() {
+        let now = Cell::new(EPOCH + 1_000);
+        let generator = SNOWFLAKE.generator(1, || now.get());
+
+        let first: u64 = generator.generate();
+        now.set(EPOCH + 500);
+        let second: u64 = generator.generate();
+        now.set(EPOCH + 2_000);
+        let third: u64 = generator.generate();
+
+        assert!(first < second && second < third);
+        assert_eq!(SNOWFLAKE.decompose(third).timestamp, EPOCH + 2_000);
+        assert_eq!(SNOWFLAKE.decompose(third).sequence, 0);
+    }
+
+    #[test]
+    fn unique_across_threads() {
+        let generator = SNOWFLAKE.generator(3, || EPOCH);
+
+        let ids: Vec<u64> = thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    s.spawn(|| {
+                        (0..10_000)
+                            .map(|_| generator.generate())
+                            .collect::<Vec<u64>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect()
+        });
+
+        assert_eq!(ids.iter().collect::<HashSet<_>>().len(), 40_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "worker does not fit within worker_bits")]
+    fn worker_out_of_range() {
+        let _ = SNOWFLAKE.generator(1 << 10, || EPOCH);
+    }
+
+    #[test]
+    #[should_panic(expected = "worker_bits + sequence_bits must not be greater than 22")]
+    fn bits_overflow() {
+        let _ = Snowflake::new(EPOCH, u32::MAX, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "worker_bits + sequence_bits must not be greater than 22")]
+    fn sequence_bits_overflow() {
+        let _ = Snowflake::new(EPOCH, 1, u32::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "clock is before the snowflake epoch")]
+    fn clock_before_epoch() {
+        let _: u64 = SNOWFLAKE.generator(0, || EPOCH - 1).generate();
+    }
+}
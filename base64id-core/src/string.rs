@@ -0,0 +1,240 @@
+use core::{borrow::Borrow, fmt, hash, ops::Deref};
+
+use crate::Error;
+
+/// A stack allocated string holding exactly `N` encoded ASCII characters
+///
+/// This is returned by the `encode` method added by the `Base64Id` derive macro,
+/// allowing an id to be used as a `&str` without allocating:
+/// ```
+/// use base64id_core::Base64Str;
+///
+/// let id = Base64Str::from_ascii(*b"AAAAAAAAAAE").unwrap();
+///
+/// assert_eq!(&*id, "AAAAAAAAAAE");
+/// assert_eq!(id.len(), 11);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Base64Str<const N: usize> {
+    bytes: [u8; N],
+}
+
+impl<const N: usize> Base64Str<N> {
+    /// Create a new string from an array of ASCII bytes
+    ///
+    /// Returns [`Error::InvalidCharacter`] if `bytes` contains any non-ASCII byte.
+    /// No other validation is done, as the alphabet used to encode `bytes` is not known.
+    pub const fn from_ascii(bytes: [u8; N]) -> Result<Self, Error> {
+        let mut i = 0;
+        while i < N {
+            if !bytes[i].is_ascii() {
+                return Err(Error::InvalidCharacter {
+                    index: i,
+                    character: bytes[i] as char,
+                });
+            }
+            i += 1;
+        }
+
+        Ok(Self { bytes })
+    }
+
+    /// Get the encoded characters as a `str`
+    #[must_use]
+    pub const fn as_str(&self) -> &str {
+        match core::str::from_utf8(&self.bytes) {
+            Ok(s) => s,
+            Err(_) => panic!("Base64Str contains non-ASCII characters"),
+        }
+    }
+
+    /// Get the encoded characters as an array of ASCII bytes
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; N] {
+        &self.bytes
+    }
+}
+
+impl<const N: usize> Deref for Base64Str<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for Base64Str<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Base64Str<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<const N: usize> Borrow<str> for Base64Str<N> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+// Hashed as a str, so that Borrow<str> may be used for map lookups
+impl<const N: usize> hash::Hash for Base64Str<N> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl<const N: usize> fmt::Display for Base64Str<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for Base64Str<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> PartialEq<str> for Base64Str<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for Base64Str<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> PartialEq<Base64Str<N>> for str {
+    fn eq(&self, other: &Base64Str<N>) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl<const N: usize> PartialEq<Base64Str<N>> for &str {
+    fn eq(&self, other: &Base64Str<N>) -> bool {
+        *self == other.as_str()
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> From<Base64Str<N>> for heapless::String<N> {
+    fn from(value: Base64Str<N>) -> Self {
+        let mut s = heapless::String::new();
+        // capacity is always equal to the length of value, so this can't fail
+        let _ = s.push_str(value.as_str());
+        s
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const N: usize> From<Base64Str<N>> for arrayvec::ArrayString<N> {
+    fn from(value: Base64Str<N>) -> Self {
+        let mut s = arrayvec::ArrayString::new();
+        // capacity is always equal to the length of value, so this can't fail
+        let _ = s.try_push_str(value.as_str());
+        s
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl<const N: usize> From<Base64Str<N>> for compact_str::CompactString {
+    fn from(value: Base64Str<N>) -> Self {
+        compact_str::CompactString::new(value.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{base64, Base64Str, Error};
+
+    #[test]
+    fn from_ascii() {
+        let s = Base64Str::from_ascii(*b"AAAAAAAAAAE").unwrap();
+
+        assert_eq!(s.as_str(), "AAAAAAAAAAE");
+        assert_eq!(s.as_bytes(), b"AAAAAAAAAAE");
+        assert_eq!(s, "AAAAAAAAAAE");
+    }
+
+    #[test]
+    fn from_ascii_invalid() {
+        assert_eq!(
+            Base64Str::from_ascii([b'A', 0xC3, 0xA9]),
+            Err(Error::InvalidCharacter {
+                index: 1,
+                character: '\u{c3}'
+            })
+        );
+    }
+
+    #[test]
+    fn ordering_matches_str() {
+        let a = Base64Str::from_ascii(base64::encode_u64_sortable(1)).unwrap();
+        let b = Base64Str::from_ascii(base64::encode_u64_sortable(2)).unwrap();
+
+        assert!(a < b);
+        assert_eq!(a.cmp(&b), a.as_str().cmp(b.as_str()));
+    }
+
+    #[test]
+    fn hash_matches_str() {
+        use core::hash::{Hash, Hasher};
+
+        struct Fnv(u64);
+
+        impl Hasher for Fnv {
+            fn finish(&self) -> u64 {
+                self.0
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                for b in bytes {
+                    self.0 = (self.0 ^ *b as u64).wrapping_mul(0x100000001b3);
+                }
+            }
+        }
+
+        let mut a = Fnv(0xcbf29ce484222325);
+        let mut b = Fnv(0xcbf29ce484222325);
+
+        Base64Str::from_ascii(*b"AAE").unwrap().hash(&mut a);
+        "AAE".hash(&mut b);
+
+        assert_eq!(a.finish(), b.finish());
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn into_heapless() {
+        let s = Base64Str::from_ascii(*b"AAAAAAAAAAE").unwrap();
+        let h: heapless::String<11> = s.into();
+
+        assert_eq!(h.as_str(), "AAAAAAAAAAE");
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn into_arrayvec() {
+        let s = Base64Str::from_ascii(*b"AAAAAAAAAAE").unwrap();
+        let a: arrayvec::ArrayString<11> = s.into();
+
+        assert_eq!(a.as_str(), "AAAAAAAAAAE");
+    }
+
+    #[cfg(feature = "compact_str")]
+    #[test]
+    fn into_compact_str() {
+        let s = Base64Str::from_ascii(*b"AAAAAAAAAAE").unwrap();
+        let c: compact_str::CompactString = s.into();
+
+        assert_eq!(c.as_str(), "AAAAAAAAAAE");
+    }
+}
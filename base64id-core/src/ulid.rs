@@ -0,0 +1,227 @@
+use core::fmt;
+
+use crate::Clock;
+
+/// Number of random bits within an id
+const RANDOM_BITS: u32 = 80;
+
+/// Number of bits storing the timestamp of an id
+const TIMESTAMP_BITS: u32 = 48;
+
+/// A source of random numbers
+///
+/// This is implemented for any `FnMut() -> u64`, so a generator may be given `rand::random` or a deterministic sequence in tests.
+pub trait Rng {
+    /// Generate the next random number
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<F: FnMut() -> u64> Rng for F {
+    fn next_u64(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// Generates time ordered 128 bit ids with the layout of a [ULID](https://github.com/ulid/spec)
+///
+/// From most to least significant bit, an id contains:
+/// - milliseconds since the UNIX epoch, within 48 bits
+/// - randomness, within 80 bits
+///
+/// Ids sort in the order they were generated, and are positive as an `i128`:
+/// ```
+/// use base64id_core::{UlidGenerator, UlidParts};
+///
+/// let mut generator = UlidGenerator::new(|| 1_700_000_000_000, || 0x0123_4567_89ab_cdef);
+/// let id: u128 = generator.generate();
+///
+/// assert_eq!(UlidParts::decompose(id).timestamp, 1_700_000_000_000);
+/// ```
+///
+/// Ids from one generator always increase.
+/// Ids generated within the same millisecond increment the randomness of the previous id, rather than drawing new randomness.
+/// When the clock moves backwards, ids continue from the last timestamp used rather than waiting for the clock to catch up.
+///
+/// The randomness of ids is only as unpredictable as the given [`Rng`].
+pub struct UlidGenerator<C, R> {
+    clock: C,
+    rng: R,
+    last: Option<u128>,
+}
+
+impl<C: Clock, R: Rng> UlidGenerator<C, R> {
+    /// Create a generator reading the time from `clock`, and drawing randomness from `rng`
+    #[must_use]
+    pub fn new(clock: C, rng: R) -> UlidGenerator<C, R> {
+        UlidGenerator {
+            clock,
+            rng,
+            last: None,
+        }
+    }
+
+    /// Generate the next id
+    ///
+    /// Ids may be converted into any type with a `From<u128>` impl, including `i128` and `u128` types deriving [`Base64Id`](https://docs.rs/base64id/latest/base64id/derive.Base64Id.html).
+    ///
+    /// # Panics
+    /// Panics if the timestamp no longer fits within 48 bits, which happens in the year 10889.
+    pub fn generate<T: From<u128>>(&mut self) -> T {
+        let now = self.clock.now_millis() as u128;
+        assert!(
+            now >> TIMESTAMP_BITS == 0,
+            "timestamp does not fit within 48 bits"
+        );
+
+        let next = match self.last {
+            // incrementing the last id carries exhausted randomness into the timestamp
+            Some(last) if now <= last >> RANDOM_BITS => last
+                .checked_add(1)
+                .expect("timestamp does not fit within 48 bits"),
+            _ => {
+                let random = ((self.rng.next_u64() as u128) << 64) | self.rng.next_u64() as u128;
+                (now << RANDOM_BITS) | (random & mask(RANDOM_BITS))
+            }
+        };
+
+        self.last = Some(next);
+
+        T::from(next)
+    }
+}
+
+impl<C, R> fmt::Debug for UlidGenerator<C, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UlidGenerator").finish_non_exhaustive()
+    }
+}
+
+/// The timestamp and randomness of an id generated by a [`UlidGenerator`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UlidParts {
+    /// Milliseconds since the UNIX epoch
+    pub timestamp: u64,
+    /// The random lower 80 bits
+    pub random: u128,
+}
+
+impl UlidParts {
+    /// Extract the timestamp and randomness from an id
+    ///
+    /// This accepts any 128 bit integer, including ids of a type deriving [`Base64Id`](https://docs.rs/base64id/latest/base64id/derive.Base64Id.html).
+    #[must_use]
+    pub fn decompose(id: impl Into<u128>) -> UlidParts {
+        let id = id.into();
+
+        UlidParts {
+            timestamp: (id >> RANDOM_BITS) as u64,
+            random: id & mask(RANDOM_BITS),
+        }
+    }
+}
+
+/// A mask of the lowest `bits` bits
+const fn mask(bits: u32) -> u128 {
+    (1 << bits) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use crate::{UlidGenerator, UlidParts};
+
+    const NOW: u64 = 1_700_000_000_000;
+
+    #[test]
+    fn layout() {
+        let mut generator = UlidGenerator::new(|| NOW, || u64::MAX);
+        let id: u128 = generator.generate();
+
+        assert_eq!(id, ((NOW as u128) << 80) | ((1 << 80) - 1));
+        assert_eq!(
+            UlidParts::decompose(id),
+            UlidParts {
+                timestamp: NOW,
+                random: (1 << 80) - 1
+            }
+        );
+        assert!((id as i128) > 0);
+    }
+
+    #[test]
+    fn monotonic_within_millisecond() {
+        let mut state = 0;
+        let mut generator = UlidGenerator::new(
+            || NOW,
+            || {
+                state += 1;
+                state
+            },
+        );
+
+        let first: u128 = generator.generate();
+        let second: u128 = generator.generate();
+        let third: u128 = generator.generate();
+
+        assert_eq!(second, first + 1);
+        assert_eq!(third, first + 2);
+        assert_eq!(UlidParts::decompose(third).timestamp, NOW);
+    }
+
+    #[test]
+    fn new_randomness_each_millisecond() {
+        let now = Cell::new(NOW);
+        let mut state = 0;
+        let mut generator = UlidGenerator::new(
+            || now.get(),
+            || {
+                state += 1;
+                state
+            },
+        );
+
+        let first: u128 = generator.generate();
+        now.set(NOW + 1);
+        let second: u128 = generator.generate();
+
+        assert_eq!(UlidParts::decompose(first).random, (1 << 64) | 2);
+        assert_eq!(UlidParts::decompose(second).random, (3 << 64) | 4);
+        assert_eq!(UlidParts::decompose(second).timestamp, NOW + 1);
+    }
+
+    #[test]
+    fn exhausted_randomness_borrows_next_millisecond() {
+        let mut generator = UlidGenerator::new(|| NOW, || u64::MAX);
+
+        let first: u128 = generator.generate();
+        let second: u128 = generator.generate();
+
+        assert_eq!(
+            UlidParts::decompose(second),
+            UlidParts {
+                timestamp: NOW + 1,
+                random: 0
+            }
+        );
+        assert!(first < second);
+    }
+
+    #[test]
+    fn monotonic_when_clock_moves_backwards() {
+        let now = Cell::new(NOW);
+        let mut generator = UlidGenerator::new(|| now.get(), || 42);
+
+        let first: u128 = generator.generate();
+        now.set(NOW - 1_000);
+        let second: u128 = generator.generate();
+
+        assert_eq!(second, first + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp does not fit within 48 bits")]
+    fn timestamp_out_of_range() {
+        let _: u128 = UlidGenerator::new(|| 1 << 48, || 0).generate();
+    }
+}
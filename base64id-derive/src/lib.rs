@@ -5,12 +5,12 @@
 #![warn(missing_docs)]
 
 use proc_macro::TokenStream;
-use proc_macro2::{Ident, Span, TokenTree};
-use quote::quote;
-use syn::{Attribute, DeriveInput, Meta};
+use proc_macro2::{Ident, Literal, Span};
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Attribute, DeriveInput, Expr, ExprLit, Lit};
 
 const ERROR_INVALID_INNER_TYPE: &str =
-    "invalid type within tuple struct, expected i128, u128, i64, u64, i32, u32, i16 or u16";
+    "invalid type within tuple struct, expected i128, u128, i64, u64, i32, u32, i16, u16, their NonZero equivalents or [u8; N]";
 
 /// Create your own base64id tuple struct
 ///
@@ -30,10 +30,30 @@ const ERROR_INVALID_INNER_TYPE: &str =
 /// [`u32`](https://doc.rust-lang.org/core/primitive.u32.html),
 /// [`u16`](https://doc.rust-lang.org/core/primitive.u16.html)
 ///
+/// `T` may also be the [`NonZero`](https://doc.rust-lang.org/core/num/struct.NonZero.html) equivalent of any of these types,
+/// such as `NonZeroU64`, `core::num::NonZeroU64` or `NonZero<u64>`, so that `Option<MyCustomId>` is the same size as `MyCustomId`.
+/// Decoding a value of zero then fails with [`Error::ZeroValue`](https://docs.rs/base64id/latest/base64id/enum.Error.html#variant.ZeroValue).
+///
+/// `T` may also be a byte array `[u8; N]`, where `N` is any non-zero integer literal.
+/// The array is encoded as `ceil(N * 8 / 6)` characters, without padding.
+///
 /// For example:
 /// ```ignore
 /// #[derive(Base64Id)]
 /// struct MyCustomId(i64);
+///
+/// #[derive(Base64Id)]
+/// struct MyDigest([u8; 32]);
+/// ```
+///
+/// Integer types may also be written as paths such as `core::primitive::u64` or `std::primitive::i32`.
+/// A type alias must be accompanied by the `inner` key, naming the aliased type:
+/// ```ignore
+/// type DbKey = i64;
+///
+/// #[derive(Base64Id)]
+/// #[base64id(inner = i64)]
+/// struct MyAliasedId(DbKey);
 /// ```
 ///
 /// ## Derive Macro Trait Implementations
@@ -57,6 +77,37 @@ const ERROR_INVALID_INNER_TYPE: &str =
 /// - 6 for 32 bit integers
 /// - 3 for 16 bit integers
 ///
+/// `TryFrom<[u8; n]>` is also added, for converting an array of `n` ASCII bytes into your tuple struct.
+///
+/// #### `encode`
+///
+/// An inherent `const fn encode(&self) -> Base64Str<n>` is added, returning the encoded id as a stack allocated [`Base64Str`](https://docs.rs/base64id/latest/base64id/struct.Base64Str.html).
+/// This dereferences to `&str` without allocating:
+/// ```ignore
+/// let encoded = MyCustomId(1).encode();
+///
+/// assert_eq!(&*encoded, "AAAAAAAAAAE");
+/// ```
+///
+/// #### `parse_lenient`
+///
+/// An inherent `fn parse_lenient(id: &str, repairs: Repairs) -> Result<(Self, Repairs), Error>` is added,
+/// for accepting ids which were damaged in transport. Each of the given [`Repairs`](https://docs.rs/base64id/latest/base64id/struct.Repairs.html)
+/// is only applied when needed, and the repairs which were applied are returned along with the id:
+/// ```ignore
+/// let (id, applied) = MyCustomId::parse_lenient(" AAAAAAAAAAE= ", Repairs::ALL)?;
+///
+/// assert_eq!(applied, Repairs::TRIM_WHITESPACE | Repairs::STRIP_PADDING);
+/// ```
+///
+/// #### `from_encoded_const`
+///
+/// An inherent `const fn from_encoded_const(id: &str) -> Self` is added, allowing ids to be decoded within a `const` or `static`.
+/// Invalid input panics, which is a compile time error when evaluated in a `const` context:
+/// ```ignore
+/// const SENTINEL: MyCustomId = MyCustomId::from_encoded_const("AAAAAAAAAAE");
+/// ```
+///
 /// #### [`PartialEq`](https://doc.rust-lang.org/core/cmp/trait.PartialEq.html), [`Eq`](https://doc.rust-lang.org/core/cmp/trait.Eq.html)
 ///
 /// These are standard impl's and have no special behaviour.
@@ -87,6 +138,25 @@ const ERROR_INVALID_INNER_TYPE: &str =
 /// In practice this means signed and unsigned positive integers will have the same decimal value when converting between them.
 /// However, signed and unsigned negative integers will have different decimal values however.
 ///
+/// Byte array structs only get the first two `From` trait impl's.
+///
+/// Structs containing a `NonZero` integer get `From` impl's for the `NonZero` integer, and for converting the struct into plain integers of either sign.
+/// Converting plain integers into the struct uses `TryFrom` instead, which fails for zero:
+///
+/// ```ignore
+/// #[derive(Base64Id)]
+/// struct MyNonZeroId(NonZeroI64);
+///
+/// impl From<MyNonZeroId> for NonZeroI64;
+/// impl From<NonZeroI64> for MyNonZeroId;
+///
+/// impl From<MyNonZeroId> for i64;
+/// impl TryFrom<i64> for MyNonZeroId;
+///
+/// impl From<MyNonZeroId> for u64;
+/// impl TryFrom<u64> for MyNonZeroId;
+/// ```
+///
 /// ## Serde Trait Implementations
 ///
 /// #### [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html), [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html)
@@ -103,85 +173,429 @@ const ERROR_INVALID_INNER_TYPE: &str =
 /// ```
 ///
 /// You can add neither, either or both traits as needed.
+///
+/// The encoded id is also accepted as ASCII bytes, for formats which provide byte strings such as CBOR.
+///
+/// Human readable formats such as JSON store the encoded string,
+/// while binary formats such as bincode or MessagePack store the inner value, as an integer or bytes.
+/// The `serde` key selects one representation for every format instead,
+/// such as `serde = string` for reading ids previously stored as strings by a binary format:
+/// ```ignore
+/// #[derive(Base64Id)]
+/// #[base64id(Serialize, Deserialize, serde = string)]
+/// struct MyStringId(i64);
+///
+/// #[derive(Base64Id)]
+/// #[base64id(Serialize, Deserialize, serde = compact)]
+/// struct MyCompactId(i64);
+/// ```
+///
+/// When deserializing the inner value, integers are checked to fit within the inner type.
+///
+/// Human readable formats only accept the encoded string.
+/// The `accept_integer` option also accepts the inner integer from formats which describe their own data, such as JSON,
+/// for clients which have not yet moved to the encoded string:
+/// ```ignore
+/// #[derive(Base64Id)]
+/// #[base64id(Deserialize(accept_integer))]
+/// struct MyMigratingId(i64);
+/// ```
+/// The inner integer of obfuscated ids is obfuscated as well, both when serialized and when accepted with `accept_integer`.
+///
+/// ## SQLx Trait Implementations
+///
+/// #### [`Type`](https://docs.rs/sqlx/latest/sqlx/trait.Type.html), [`Encode`](https://docs.rs/sqlx/latest/sqlx/trait.Encode.html), [`Decode`](https://docs.rs/sqlx/latest/sqlx/trait.Decode.html)
+///
+/// The `sqlx` key adds SQLx trait implementations for every database, which store the inner value rather than the encoded string.
+/// To do this you must include SQLx as a dependency in your Cargo.toml file.
+/// ```ignore
+/// #[derive(Base64Id)]
+/// #[base64id(sqlx)]
+/// struct MyCustomId(i64);
+/// ```
+///
+/// Postgres lacks unsigned integers, so unsigned integers are stored as the signed integer of the same size, using the `From` conversions above.
+/// 128 bit integers and byte arrays are stored as big endian bytes, such as a `BYTEA` or `BLOB` column.
+///
+/// ## Diesel Trait Implementations
+///
+/// #### [`AsExpression`](https://docs.rs/diesel/latest/diesel/expression/trait.AsExpression.html), [`ToSql`](https://docs.rs/diesel/latest/diesel/serialize/trait.ToSql.html), [`FromSql`](https://docs.rs/diesel/latest/diesel/deserialize/trait.FromSql.html), [`FromSqlRow`](https://docs.rs/diesel/latest/diesel/deserialize/trait.FromSqlRow.html)
+///
+/// The `diesel` key adds Diesel trait implementations, which store integers as the `SmallInt`, `Integer` or `BigInt` SQL type of the same size.
+/// Given `diesel = Text`, the encoded string is stored instead.
+/// To do this you must include Diesel as a dependency in your Cargo.toml file.
+/// ```ignore
+/// #[derive(Base64Id)]
+/// #[base64id(diesel)]
+/// struct MyCustomId(i64);
+///
+/// #[derive(Base64Id)]
+/// #[base64id(diesel = Text)]
+/// struct MyTextId(i64);
+/// ```
+///
+/// As with SQLx, unsigned integers are stored as the signed integer of the same size.
+/// 128 bit integers and byte arrays must be stored as `Text`.
+///
+/// Ids may be used within queries on every backend.
+/// `ToSql` for signed integers is also implemented for every backend.
+/// Unsigned integers, non-zero integers and `Text` are converted before they are bound,
+/// which is implemented separately for each backend.
+/// Binding these ids, such as a nullable id or one within an `Insertable` struct, requires the `diesel_sqlite`, `diesel_postgres` or `diesel_mysql` feature of this crate
+/// for each backend you use, along with the matching backend feature of Diesel:
+/// ```toml
+/// [dependencies]
+/// base64id = { version = "0.4", features = ["diesel_postgres"] }
+/// ```
+///
+/// ## rusqlite Trait Implementations
+///
+/// #### [`ToSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.ToSql.html), [`FromSql`](https://docs.rs/rusqlite/latest/rusqlite/types/trait.FromSql.html)
+///
+/// The `rusqlite` key adds rusqlite trait implementations, which store integers as an `INTEGER`.
+/// Given `rusqlite = Text`, the encoded string is stored instead, and errors decoding it are returned within `FromSqlError::Other`.
+/// To do this you must include rusqlite as a dependency in your Cargo.toml file.
+/// ```ignore
+/// #[derive(Base64Id)]
+/// #[base64id(rusqlite)]
+/// struct MyCustomId(i64);
+///
+/// #[derive(Base64Id)]
+/// #[base64id(rusqlite = Text)]
+/// struct MyTextId(i64);
+/// ```
+///
+/// As with SQLx, unsigned integers are stored as the signed integer of the same size.
+/// SQLite integers are at most 64 bits, so 128 bit integers are stored as a 16 byte big endian `BLOB`, and byte arrays are also stored as a `BLOB`.
+///
+/// ## postgres-types Trait Implementations
+///
+/// #### [`ToSql`](https://docs.rs/postgres-types/latest/postgres_types/trait.ToSql.html), [`FromSql`](https://docs.rs/postgres-types/latest/postgres_types/trait.FromSql.html)
+///
+/// The `postgres` key adds the trait implementations used by `tokio-postgres` and `postgres`.
+/// To do this you must include postgres-types as a dependency in your Cargo.toml file.
+/// ```ignore
+/// #[derive(Base64Id)]
+/// #[base64id(postgres)]
+/// struct MyCustomId(i64);
+/// ```
+///
+/// Integers are stored as the `int2`, `int4` or `int8` of the same size, with unsigned integers stored as the signed integer of the same size.
+/// 128 bit integers are stored as big endian bytes within either a `uuid` or `bytea` column, as are byte arrays of 16 bytes.
+/// Other byte arrays are stored within a `bytea` column.
+///
+/// ## JSON Schema Trait Implementations
+///
+/// #### [`JsonSchema`](https://docs.rs/schemars/latest/schemars/trait.JsonSchema.html)
+///
+/// The `JsonSchema` key adds a schemars trait implementation, describing the encoded string with its exact length and a `pattern`.
+/// To do this you must include schemars as a dependency in your Cargo.toml file.
+/// ```ignore
+/// #[derive(Base64Id)]
+/// #[base64id(JsonSchema)]
+/// struct MyCustomId(i64);
+/// ```
+///
+/// The pattern restricts the last character to those allowed by the size of the inner type, such as `[AEIMQUYcgkosw048]` for 64 bit integers,
+/// and includes any prefix or check character. The schema gives the encoded zero id as an example, or the id of one for non-zero types.
+/// Given `serde = compact`, the schema of the inner type is used instead,
+/// and given `Deserialize(accept_integer)`, the schema accepts either the encoded string or the inner integer with `anyOf`.
+///
+/// ## Alphabets
+///
+/// By default ids are encoded with the [base64url](https://datatracker.ietf.org/doc/html/rfc4648#section-5) alphabet.
+/// A different [`Alphabet`](https://docs.rs/base64id/latest/base64id/struct.Alphabet.html) can be selected with the `alphabet` key,
+/// given either a constant `Alphabet` or a string literal of 64 unique characters:
+/// ```ignore
+/// #[derive(Base64Id)]
+/// #[base64id(alphabet = base64id::Alphabet::STANDARD)]
+/// struct MyStandardId(i64);
+///
+/// #[derive(Base64Id)]
+/// #[base64id(alphabet = "_-9876543210zyxwvutsrqponmlkjihgfedcbaZYXWVUTSRQPONMLKJIHGFEDCBA")]
+/// struct MyShuffledId(i64);
+/// ```
+///
+/// Invalid alphabets are rejected at compile time.
+///
+/// ## Sortable Encoding
+///
+/// Strings encoded with the base64url alphabet do not sort in the same order as the integers they represent.
+/// The `sortable` key selects an order preserving encoding instead, for use as sorted string keys:
+/// ```ignore
+/// #[derive(Base64Id)]
+/// #[base64id(sortable)]
+/// struct MySortableId(i64);
+/// ```
+///
+/// This uses the ASCII ordered [`Alphabet::SORTABLE`](https://docs.rs/base64id/latest/base64id/struct.Alphabet.html#associatedconstant.SORTABLE).
+/// For signed integers the sign bit is also flipped, so that negative values sort before positive values.
+/// Byte arrays sort in the same order as their bytes.
+/// The `sortable` key cannot be combined with the `alphabet` key.
+///
+/// ## Obfuscation
+///
+/// Sequential integers encode to sequential strings, which reveal how many ids exist.
+/// The `obfuscate` key applies a keyed permutation to the integer before it is encoded, and reverses it after decoding.
+/// It is given any expression which dereferences to a [`Key`](https://docs.rs/base64id/latest/base64id/struct.Key.html),
+/// such as a constant, a static initialized at runtime or a function call:
+/// ```ignore
+/// const KEY: Key = Key::new(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+///
+/// #[derive(Base64Id)]
+/// #[base64id(obfuscate = KEY)]
+/// struct MyObfuscatedId(i64);
+///
+/// static RUNTIME_KEY: LazyLock<Key> = LazyLock::new(load_key);
+///
+/// #[derive(Base64Id)]
+/// #[base64id(obfuscate = RUNTIME_KEY)]
+/// struct MyRuntimeObfuscatedId(i64);
+/// ```
+///
+/// As the key may only be known at runtime, obfuscated structs don't get `from_encoded_const`, and `encode` is not a `const fn`.
+/// The `obfuscate` key is only supported for integer types, and cannot be combined with the `sortable` key.
+///
+/// ## Check Characters
+///
+/// Ids which are copied by hand are easily mistyped.
+/// The `checksum` key appends a check character to the encoded id, which is verified when decoding:
+/// ```ignore
+/// #[derive(Base64Id)]
+/// #[base64id(checksum)]
+/// struct MyCheckedId(i64);
+///
+/// assert_eq!(MyCheckedId(1).to_string(), "AAAAAAAAAAE4");
+/// ```
+///
+/// The check character is calculated with the Luhn mod 64 algorithm, which detects every single mistyped character and most swapped adjacent characters.
+/// Decoding an id with a mismatched check character fails with [`Error::ChecksumMismatch`](https://docs.rs/base64id/latest/base64id/enum.Error.html#variant.ChecksumMismatch).
+/// Encoded ids are one character longer, including within `Base64Str<n>`, `TryFrom<[char; n]>` and `TryFrom<[u8; n]>`.
+/// The `checksum` key may be combined with any other key.
+///
+/// ## Prefixes
+///
+/// Ids of different types are easily confused within logs, URLs and support tickets.
+/// The `prefix` key adds a self describing prefix to the encoded id, which must be a non-empty ASCII string:
+/// ```ignore
+/// #[derive(Base64Id)]
+/// #[base64id(prefix = "usr_")]
+/// struct UserId(i64);
+///
+/// assert_eq!(UserId(1).to_string(), "usr_AAAAAAAAAAE");
+/// ```
+///
+/// Decoding an id without the prefix fails with [`Error::InvalidPrefix`](https://docs.rs/base64id/latest/base64id/enum.Error.html#variant.InvalidPrefix).
+/// The prefix is included within `Base64Str<n>`, `TryFrom<[char; n]>` and `TryFrom<[u8; n]>`, and precedes any check character.
 #[proc_macro_derive(Base64Id, attributes(base64id))]
 pub fn tuple_struct_into_base64id(input: TokenStream) -> TokenStream {
-    let ast: DeriveInput = syn::parse(input).expect("failed to parse token stream");
+    let ast = syn::parse_macro_input!(input as DeriveInput);
+
+    match expand(ast) {
+        Ok(implementation) => implementation.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
 
+/// Generate the implementation of `#[derive(Base64Id)]`
+///
+/// Returns every error found within the struct and its attributes, combined into one error.
+fn expand(ast: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let ident = ast.ident;
-    let struct_inner_type = get_validated_struct_data(ast.data);
-    let struct_inner_type_string = struct_inner_type.to_string();
-
-    let char_len = match struct_inner_type_string.as_str() {
-        "i128" | "u128" => 22,
-        "i64" | "u64" => 11,
-        "i32" | "u32" => 6,
-        "i16" | "u16" => 3,
-        _ => panic!("{ERROR_INVALID_INNER_TYPE}"),
-    };
-
-    let (encode_fn, decode_fn, char_array_type, struct_inner_type_alt) =
-        match struct_inner_type_string.as_str() {
-            "i128" => (
-                quote! {::base64id::base64::encode_i128},
-                quote! {::base64id::base64::decode_i128},
-                quote! {[char; #char_len]},
-                quote! {u128},
-            ),
-            "u128" => (
-                quote! {::base64id::base64::encode_u128},
-                quote! {::base64id::base64::decode_u128},
-                quote! {[char; #char_len]},
-                quote! {i128},
-            ),
-            "i64" => (
-                quote! {::base64id::base64::encode_i64},
-                quote! {::base64id::base64::decode_i64},
-                quote! {[char; #char_len]},
-                quote! {u64},
-            ),
-            "u64" => (
-                quote! {::base64id::base64::encode_u64},
-                quote! {::base64id::base64::decode_u64},
-                quote! {[char; #char_len]},
-                quote! {i64},
-            ),
-            "i32" => (
-                quote! {::base64id::base64::encode_i32},
-                quote! {::base64id::base64::decode_i32},
-                quote! {[char; #char_len]},
-                quote! {u32},
-            ),
-            "u32" => (
-                quote! {::base64id::base64::encode_u32},
-                quote! {::base64id::base64::decode_u32},
-                quote! {[char; #char_len]},
-                quote! {i32},
-            ),
-            "i16" => (
-                quote! {::base64id::base64::encode_i16},
-                quote! {::base64id::base64::decode_i16},
-                quote! {[char; #char_len]},
-                quote! {u16},
-            ),
-            "u16" => (
-                quote! {::base64id::base64::encode_u16},
-                quote! {::base64id::base64::decode_u16},
-                quote! {[char; #char_len]},
-                quote! {i16},
-            ),
-            _ => panic!("{ERROR_INVALID_INNER_TYPE}"),
+
+    let mut errors = Vec::new();
+
+    let field_type = get_validated_struct_field(ast.data);
+    let options = evaluate_attributes(&ast.attrs, &mut errors);
+
+    let types = field_type.and_then(|field_type| {
+        let inner_type = match &options.inner {
+            Some(inner) => get_validated_inner_type(inner, false)?,
+            None => get_validated_inner_type(&field_type, true)?,
         };
+        Ok((field_type, inner_type))
+    });
 
-    let mut implementation = quote! {
-        impl ::core::fmt::Display for #ident {
-            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                use ::core::fmt::Write;
+    match &types {
+        Ok((_, inner_type)) => validate_options(inner_type, &options, &mut errors),
+        Err(e) => errors.insert(0, e.clone()),
+    }
+
+    combine_errors(errors)?;
+    let (field_type, inner_type) = types?;
+
+    let (struct_inner_type, char_len) = match &inner_type {
+        InnerType::Integer(t) | InnerType::NonZero(t) => {
+            let char_len = match t.to_string().as_str() {
+                "i128" | "u128" => 22,
+                "i64" | "u64" => 11,
+                "i32" | "u32" => 6,
+                "i16" | "u16" => 3,
+                _ => unreachable!("integer types are validated by get_validated_inner_type"),
+            };
+
+            match inner_type {
+                InnerType::NonZero(_) => (quote! {::core::num::NonZero<#t>}, char_len),
+                _ => (quote! {#t}, char_len),
+            }
+        }
+        InnerType::Bytes(n) => {
+            let len = Literal::usize_unsuffixed(*n);
+            (quote! {[u8; #len]}, base64id_core::base64::encoded_len(*n))
+        }
+    };
+
+    // the check character follows the encoded value, and the prefix precedes both
+    let payload_len = char_len;
+    let checked_len = payload_len + usize::from(options.checksum);
+    let char_len = options.prefix.as_ref().map_or(0, String::len) + checked_len;
+
+    let (encode_body, decode_body, alphabet) = match (
+        &inner_type,
+        options.sortable,
+        &options.alphabet,
+    ) {
+        (_, true, Some(_)) => {
+            unreachable!("sortable and alphabet keys are rejected by validate_options")
+        }
+        (InnerType::Bytes(n), sortable, alphabet) => {
+            let n = Literal::usize_unsuffixed(*n);
+            let m = Literal::usize_unsuffixed(payload_len);
+
+            (
+                quote! {::base64id::base64::encode_array::<#n, #m>(&value, &Self::BASE64ID_ALPHABET)},
+                quote! {::base64id::base64::decode_array::<#n, #m>(input, &Self::BASE64ID_ALPHABET)},
+                match (sortable, alphabet) {
+                    (_, Some(a)) => a.clone(),
+                    (true, None) => quote! {::base64id::Alphabet::SORTABLE},
+                    (false, None) => quote! {::base64id::Alphabet::BASE64URL},
+                },
+            )
+        }
+        (InnerType::Integer(t) | InnerType::NonZero(t), true, None) => {
+            let struct_inner_type_string = t.to_string();
+            let encode_fn = base64_fn(&format!("encode_{struct_inner_type_string}_sortable"));
+            let decode_fn = base64_fn(&format!("decode_{struct_inner_type_string}_sortable"));
+
+            (
+                quote! {#encode_fn(value)},
+                quote! {#decode_fn(input)},
+                quote! {::base64id::Alphabet::SORTABLE},
+            )
+        }
+        (InnerType::Integer(t) | InnerType::NonZero(t), false, alphabet) => {
+            let struct_inner_type_string = t.to_string();
+            let encode_fn = base64_fn(&format!("encode_{struct_inner_type_string}_with"));
+            let decode_fn = base64_fn(&format!("decode_{struct_inner_type_string}_with"));
+
+            (
+                quote! {#encode_fn(value, &Self::BASE64ID_ALPHABET)},
+                quote! {#decode_fn(input, &Self::BASE64ID_ALPHABET)},
+                match alphabet {
+                    Some(a) => a.clone(),
+                    None => quote! {::base64id::Alphabet::BASE64URL},
+                },
+            )
+        }
+    };
+
+    let (encode_body, decode_body) = match (&inner_type, &options.obfuscate) {
+        (_, None) => (encode_body, decode_body),
+        (InnerType::Bytes(_), Some(_)) => {
+            unreachable!("obfuscated byte arrays are rejected by validate_options")
+        }
+        (InnerType::Integer(t) | InnerType::NonZero(t), Some(key)) => {
+            let obfuscate = Ident::new(&format!("obfuscate_{t}"), Span::call_site());
+            let deobfuscate = Ident::new(&format!("deobfuscate_{t}"), Span::call_site());
 
-                for c in #encode_fn(self.0) {
-                    f.write_char(c)?;
+            (
+                quote! {
+                    let value = ::base64id::Key::#obfuscate(&(#key), value);
+                    #encode_body
+                },
+                quote! {
+                    match #decode_body {
+                        Ok(v) => Ok(::base64id::Key::#deobfuscate(&(#key), v)),
+                        Err(e) => Err(e),
+                    }
+                },
+            )
+        }
+    };
+
+    let (encode_body, decode_body) = match inner_type {
+        InnerType::NonZero(_) => (
+            quote! {
+                let value = value.get();
+                #encode_body
+            },
+            quote! {
+                match #decode_body {
+                    Ok(v) => match ::core::num::NonZero::new(v) {
+                        Some(v) => Ok(v),
+                        None => Err(::base64id::Error::ZeroValue),
+                    },
+                    Err(e) => Err(e),
+                }
+            },
+        ),
+        _ => (encode_body, decode_body),
+    };
+
+    let (encode_body, decode_body) = match options.checksum {
+        true => (
+            quote! {
+                let encoded: [u8; #payload_len] = { #encode_body };
+                ::base64id::base64::append_check(encoded, &Self::BASE64ID_ALPHABET)
+            },
+            quote! {
+                match ::base64id::base64::verify_check::<#payload_len>(input, &Self::BASE64ID_ALPHABET) {
+                    Ok(input) => #decode_body,
+                    Err(e) => Err(e),
+                }
+            },
+        ),
+        false => (encode_body, decode_body),
+    };
+
+    let (encode_body, decode_body) = match &options.prefix {
+        Some(prefix) => (
+            {
+                let prefix_bytes = Literal::byte_string(prefix.as_bytes());
+                quote! {
+                    let encoded: [u8; #checked_len] = { #encode_body };
+                    ::base64id::base64::prepend_prefix(encoded, #prefix_bytes)
                 }
+            },
+            quote! {
+                match ::base64id::base64::strip_prefix(input, #prefix) {
+                    Ok(input) => match #decode_body {
+                        Ok(v) => Ok(v),
+                        Err(e) => Err(::base64id::base64::prefix_error(e, #prefix)),
+                    },
+                    Err(e) => Err(e),
+                }
+            },
+        ),
+        None => (encode_body, decode_body),
+    };
+
+    // keys may be supplied at runtime, so obfuscated ids can't be encoded or decoded within a const context
+    let constness = match options.obfuscate {
+        Some(_) => quote! {},
+        None => quote! {const},
+    };
+
+    // the prefix is checked before lenient repairs are applied, so that they never alter it
+    let prefix = options.prefix.as_deref().unwrap_or("");
+
+    let char_array_type = quote! {[char; #char_len]};
+    let byte_array_type = quote! {[u8; #char_len]};
 
-                Ok(())
+    let mut implementation = quote! {
+        impl ::core::fmt::Display for #ident {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(&self.encode())
             }
         }
 
@@ -197,23 +611,19 @@ pub fn tuple_struct_into_base64id(input: TokenStream) -> TokenStream {
             }
         }
 
-        impl ::core::convert::From<#ident> for #struct_inner_type_alt {
-            fn from(id: #ident) -> Self {
-                #struct_inner_type_alt::from_be_bytes(id.0.to_be_bytes())
-            }
-        }
+        impl ::core::convert::TryFrom<#char_array_type> for #ident {
+            type Error = ::base64id::Error;
 
-        impl ::core::convert::From<#struct_inner_type_alt> for #ident {
-            fn from(id: #struct_inner_type_alt) -> Self {
-                Self(#struct_inner_type::from_be_bytes(id.to_be_bytes()))
+            fn try_from(input: #char_array_type) -> ::core::result::Result<Self, Self::Error> {
+                Self::try_from(::base64id::base64::ascii_from_chars(input)?)
             }
         }
 
-        impl ::core::convert::TryFrom<#char_array_type> for #ident {
+        impl ::core::convert::TryFrom<#byte_array_type> for #ident {
             type Error = ::base64id::Error;
 
-            fn try_from(input: #char_array_type) -> ::core::result::Result<Self, Self::Error> {
-                Ok(Self(#decode_fn(input)?))
+            fn try_from(input: #byte_array_type) -> ::core::result::Result<Self, Self::Error> {
+                Ok(Self(Self::base64id_decode(&input)?))
             }
         }
 
@@ -221,21 +631,10 @@ pub fn tuple_struct_into_base64id(input: TokenStream) -> TokenStream {
             type Err = ::base64id::Error;
 
             fn from_str(id: &str) -> ::core::result::Result<Self, Self::Err> {
-                let mut array: #char_array_type = ::core::default::Default::default();
-                let mut id_iter = id.chars();
-
-                for c in array.iter_mut() {
-                    *c = match id_iter.next() {
-                        Some(d) => d,
-                        None => return Err(::base64id::Error::InvalidLength),
-                    };
+                match Self::base64id_decode(id.as_bytes()) {
+                    Ok(v) => Ok(Self(v)),
+                    Err(e) => Err(::base64id::base64::error_for_str(e, id)),
                 }
-
-                if id_iter.next().is_some() {
-                    return Err(::base64id::Error::InvalidLength);
-                }
-
-                #ident::try_from(array)
             }
         }
 
@@ -245,72 +644,565 @@ pub fn tuple_struct_into_base64id(input: TokenStream) -> TokenStream {
             }
         }
         impl ::core::cmp::Eq for #ident {}
+
+        impl #ident {
+            #[allow(dead_code)]
+            const BASE64ID_ALPHABET: ::base64id::Alphabet = #alphabet;
+
+            #constness fn base64id_encode(value: #struct_inner_type) -> #byte_array_type {
+                #encode_body
+            }
+
+            #constness fn base64id_decode(
+                input: &[u8],
+            ) -> ::core::result::Result<#struct_inner_type, ::base64id::Error> {
+                #decode_body
+            }
+
+            /// Encode as a stack allocated string
+            #[must_use]
+            pub #constness fn encode(&self) -> ::base64id::Base64Str<#char_len> {
+                match ::base64id::Base64Str::from_ascii(Self::base64id_encode(self.0)) {
+                    Ok(s) => s,
+                    Err(_) => panic!("alphabet contains non-ASCII characters"),
+                }
+            }
+
+            /// Decode a base64url string after repairing common transport damage
+            ///
+            /// Only the given `repairs` are applied, and only when needed.
+            /// The repairs which were applied are returned along with the id.
+            pub fn parse_lenient(
+                id: &str,
+                repairs: ::base64id::Repairs,
+            ) -> ::core::result::Result<(Self, ::base64id::Repairs), ::base64id::Error> {
+                let (v, applied) = ::base64id::base64::decode_lenient_prefixed::<_, #char_len>(
+                    id,
+                    #prefix,
+                    repairs,
+                    &Self::BASE64ID_ALPHABET,
+                    Self::base64id_decode,
+                )?;
+
+                Ok((Self(v), applied))
+            }
+        }
     };
 
-    evaluate_attributes(&ident, ast.attrs, char_len, &mut implementation);
+    // an alias must name the type given by the inner key
+    if options.inner.is_some() {
+        implementation.extend(quote_spanned! {field_type.span()=>
+            const _: fn(#field_type) -> #struct_inner_type = |inner| inner;
+        });
+    }
+
+    if options.obfuscate.is_none() {
+        apply_from_encoded_const(&ident, char_len, &mut implementation);
+    }
+
+    match &inner_type {
+        InnerType::Integer(t) => apply_sign_conversions(&ident, t, &mut implementation),
+        InnerType::NonZero(t) => apply_nonzero_conversions(&ident, t, &mut implementation),
+        InnerType::Bytes(_) => (),
+    }
+
+    if options.serialize {
+        apply_serialize_trait(&ident, &inner_type, &options, &mut implementation);
+    }
+
+    if options.json_schema {
+        apply_json_schema_trait(
+            &ident,
+            &inner_type,
+            &struct_inner_type,
+            payload_len,
+            char_len,
+            &options,
+            &mut implementation,
+        );
+    }
+
+    if options.sqlx {
+        apply_sqlx_traits(&ident, &inner_type, &mut implementation);
+    }
+
+    if let Some(storage) = &options.diesel {
+        apply_diesel_traits(&ident, &inner_type, storage, &mut implementation);
+    }
+
+    if let Some(storage) = &options.rusqlite {
+        apply_rusqlite_traits(&ident, &inner_type, storage, &mut implementation);
+    }
+
+    if options.postgres {
+        apply_postgres_traits(&ident, &inner_type, &mut implementation);
+    }
+
+    if options.deserialize {
+        apply_deserialize_trait(&ident, &inner_type, char_len, &options, &mut implementation);
+    }
 
-    implementation.into()
+    Ok(implementation)
 }
 
-/// Determines if the base64id attribute is present
-/// and if it contains expected keywords
-fn evaluate_attributes(
-    ident: &proc_macro2::Ident,
-    attrs: Vec<Attribute>,
-    char_len: usize,
-    implementation: &mut proc_macro2::TokenStream,
-) {
-    for attr in attrs {
-        let attr_ident = match attr.path().get_ident() {
-            Some(i) => i,
-            None => continue,
-        };
+/// Ensure the given options may be combined with each other and with the inner type, adding any errors to `errors`
+fn validate_options(inner_type: &InnerType, options: &Options, errors: &mut Vec<syn::Error>) {
+    if let (true, Some(alphabet)) = (options.sortable, &options.alphabet) {
+        errors.push(syn::Error::new_spanned(
+            alphabet,
+            "the sortable and alphabet keys cannot be combined",
+        ));
+    }
 
-        if attr_ident != "base64id" {
-            continue;
+    if let Some(key) = &options.obfuscate {
+        if options.sortable {
+            errors.push(syn::Error::new_spanned(
+                key,
+                "the sortable and obfuscate keys cannot be combined",
+            ));
         }
 
-        let meta_list = match attr.meta {
-            Meta::List(l) => l,
-            _ => continue,
-        };
+        if let InnerType::Bytes(_) = inner_type {
+            errors.push(syn::Error::new_spanned(
+                key,
+                "the obfuscate key is only supported for integer types",
+            ));
+        }
+    }
 
-        for token in meta_list.tokens {
-            let token_ident = match token {
-                TokenTree::Ident(i) => i,
-                _ => continue,
-            };
+    if let (Some(span), InnerType::Bytes(_)) = (options.accept_integer, inner_type) {
+        errors.push(syn::Error::new(
+            span,
+            "accept_integer is only supported for integer types",
+        ));
+    }
 
-            if token_ident == "Serialize" {
-                apply_serialize_trait(ident, implementation);
-            }
+    if let Some(SqlStorage::Inner(span)) = options.diesel {
+        let stored_as_integer = match inner_type {
+            InnerType::Integer(t) | InnerType::NonZero(t) => !t.to_string().ends_with("128"),
+            InnerType::Bytes(_) => false,
+        };
 
-            if token_ident == "Deserialize" {
-                apply_deserialize_trait(ident, char_len, implementation);
-            }
+        if !stored_as_integer {
+            errors.push(syn::Error::new(
+                span,
+                "128 bit integers and byte arrays must be stored with `diesel = Text`",
+            ));
         }
+    }
+}
 
-        return;
+/// Combine errors into one, so that all of them are reported together
+///
+/// Returns `Ok` if there are no errors.
+fn combine_errors(errors: impl IntoIterator<Item = syn::Error>) -> syn::Result<()> {
+    let mut errors = errors.into_iter();
+
+    match errors.next() {
+        Some(mut combined) => {
+            combined.extend(errors);
+            Err(combined)
+        }
+        None => Ok(()),
     }
 }
 
-/// Enable the following syntax:
-/// ```ignore
-/// #[derive(base64id::Base64Id)]
-/// #[base64id(Serialize)]
-/// struct MyType(i64);
-/// ```
-fn apply_serialize_trait(
+/// Add a `from_encoded_const` const constructor
+fn apply_from_encoded_const(
     ident: &proc_macro2::Ident,
+    char_len: usize,
     implementation: &mut proc_macro2::TokenStream,
 ) {
-    implementation.extend(quote!(
+    implementation.extend(quote! {
+        impl #ident {
+            /// Decode a base64url string within a `const` context
+            ///
+            /// # Panics
+            /// Panics if `id` is not a valid encoded string. When evaluated as a `const` or `static`, this is a compile time error.
+            #[must_use]
+            pub const fn from_encoded_const(id: &str) -> Self {
+                match Self::base64id_decode(id.as_bytes()) {
+                    Ok(v) => Self(v),
+                    Err(::base64id::Error::InvalidLength { .. }) => panic!(concat!(
+                        "invalid ",
+                        stringify!(#ident),
+                        " string. expected exactly ",
+                        #char_len,
+                        " characters"
+                    )),
+                    Err(::base64id::Error::InvalidCharacter { .. }) => panic!(concat!(
+                        "invalid ",
+                        stringify!(#ident),
+                        " string. contains a character outside of the alphabet"
+                    )),
+                    Err(::base64id::Error::OutOfBoundsCharacter { .. }) => panic!(concat!(
+                        "invalid ",
+                        stringify!(#ident),
+                        " string. the last character is out of bounds"
+                    )),
+                    Err(::base64id::Error::ZeroValue) => panic!(concat!(
+                        "invalid ",
+                        stringify!(#ident),
+                        " string. decodes to zero"
+                    )),
+                    Err(::base64id::Error::ChecksumMismatch) => panic!(concat!(
+                        "invalid ",
+                        stringify!(#ident),
+                        " string. check character does not match"
+                    )),
+                    Err(::base64id::Error::InvalidPrefix { .. }) => panic!(concat!(
+                        "invalid ",
+                        stringify!(#ident),
+                        " string. does not start with the expected prefix"
+                    )),
+                    Err(_) => panic!(concat!("invalid ", stringify!(#ident), " string")),
+                }
+            }
+        }
+    });
+}
+
+/// Get the integer type of the opposite sign
+fn opposite_sign_type(struct_inner_type: &proc_macro2::Ident) -> proc_macro2::TokenStream {
+    match struct_inner_type.to_string().as_str() {
+        "i128" => quote! {u128},
+        "u128" => quote! {i128},
+        "i64" => quote! {u64},
+        "u64" => quote! {i64},
+        "i32" => quote! {u32},
+        "u32" => quote! {i32},
+        "i16" => quote! {u16},
+        "u16" => quote! {i16},
+        _ => unreachable!("integer types are validated by get_validated_inner_type"),
+    }
+}
+
+/// Add `From` conversions between the struct and the integer of the opposite sign
+fn apply_sign_conversions(
+    ident: &proc_macro2::Ident,
+    struct_inner_type: &proc_macro2::Ident,
+    implementation: &mut proc_macro2::TokenStream,
+) {
+    let struct_inner_type_alt = opposite_sign_type(struct_inner_type);
+
+    implementation.extend(quote! {
+        impl ::core::convert::From<#ident> for #struct_inner_type_alt {
+            fn from(id: #ident) -> Self {
+                #struct_inner_type_alt::from_be_bytes(id.0.to_be_bytes())
+            }
+        }
+
+        impl ::core::convert::From<#struct_inner_type_alt> for #ident {
+            fn from(id: #struct_inner_type_alt) -> Self {
+                Self(#struct_inner_type::from_be_bytes(id.to_be_bytes()))
+            }
+        }
+    });
+}
+
+/// Add conversions between a struct containing a `NonZero` integer and plain integers of either sign
+///
+/// Converting from a plain integer fails with `Error::ZeroValue` when given zero.
+fn apply_nonzero_conversions(
+    ident: &proc_macro2::Ident,
+    integer_type: &proc_macro2::Ident,
+    implementation: &mut proc_macro2::TokenStream,
+) {
+    let integer_type_alt = opposite_sign_type(integer_type);
+
+    implementation.extend(quote! {
+        impl ::core::convert::From<#ident> for #integer_type {
+            fn from(id: #ident) -> Self {
+                id.0.get()
+            }
+        }
+
+        impl ::core::convert::TryFrom<#integer_type> for #ident {
+            type Error = ::base64id::Error;
+
+            fn try_from(id: #integer_type) -> ::core::result::Result<Self, Self::Error> {
+                match ::core::num::NonZero::new(id) {
+                    Some(v) => Ok(Self(v)),
+                    None => Err(::base64id::Error::ZeroValue),
+                }
+            }
+        }
+
+        impl ::core::convert::From<#ident> for #integer_type_alt {
+            fn from(id: #ident) -> Self {
+                #integer_type_alt::from_be_bytes(id.0.get().to_be_bytes())
+            }
+        }
+
+        impl ::core::convert::TryFrom<#integer_type_alt> for #ident {
+            type Error = ::base64id::Error;
+
+            fn try_from(id: #integer_type_alt) -> ::core::result::Result<Self, Self::Error> {
+                Self::try_from(#integer_type::from_be_bytes(id.to_be_bytes()))
+            }
+        }
+    });
+}
+
+/// Path to a function within the `base64id::base64` module
+fn base64_fn(name: &str) -> proc_macro2::TokenStream {
+    let name = Ident::new(name, Span::call_site());
+    quote! {::base64id::base64::#name}
+}
+
+/// Options set through the base64id derive macro helper attribute
+#[derive(Default)]
+struct Options {
+    serialize: bool,
+    deserialize: bool,
+    serde: SerdeFormat,
+    accept_integer: Option<Span>,
+    json_schema: bool,
+    alphabet: Option<proc_macro2::TokenStream>,
+    sortable: bool,
+    obfuscate: Option<proc_macro2::TokenStream>,
+    checksum: bool,
+    prefix: Option<String>,
+    inner: Option<syn::Type>,
+    sqlx: bool,
+    diesel: Option<SqlStorage>,
+    rusqlite: Option<SqlStorage>,
+    postgres: bool,
+}
+
+/// The representations used by the serde traits added with the Serialize and Deserialize keys
+#[derive(Default)]
+enum SerdeFormat {
+    /// The encoded string for human readable formats, and the inner value for other formats
+    #[default]
+    Auto,
+    /// Always the encoded string
+    String,
+    /// Always the inner value
+    Compact,
+}
+
+/// How ids are stored by the database traits added with the diesel and rusqlite keys
+enum SqlStorage {
+    /// The inner value, such as an integer of the same size. Holds the span of the key
+    Inner(Span),
+    /// The encoded string, as `Text`
+    Text,
+}
+
+/// Parses the value of a database key, which is either absent or `Text`
+///
+/// Returns `None` after adding an error to `errors` if the value is not `Text`.
+fn parse_sql_storage(
+    meta: &syn::meta::ParseNestedMeta,
+    errors: &mut Vec<syn::Error>,
+) -> syn::Result<Option<SqlStorage>> {
+    if !meta.input.peek(syn::Token![=]) {
+        return Ok(Some(SqlStorage::Inner(meta.path.span())));
+    }
+
+    let sql_type: Ident = meta.value()?.parse()?;
+
+    if sql_type != "Text" {
+        let key = meta
+            .path
+            .get_ident()
+            .map(Ident::to_string)
+            .unwrap_or_default();
+        errors.push(syn::Error::new(
+            sql_type.span(),
+            format!("unsupported {key} SQL type. expected `Text`"),
+        ));
+        return Ok(None);
+    }
+
+    Ok(Some(SqlStorage::Text))
+}
+
+/// Parses all base64id attributes into a set of [`Options`]
+///
+/// Every invalid key found within the attributes is added to `errors`.
+fn evaluate_attributes(attrs: &[Attribute], errors: &mut Vec<syn::Error>) -> Options {
+    let mut options = Options::default();
+
+    for attr in attrs {
+        if !attr.path().is_ident("base64id") {
+            continue;
+        }
+
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("Serialize") {
+                options.serialize = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("Deserialize") {
+                options.deserialize = true;
+
+                if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|nested| {
+                        match nested.path.is_ident("accept_integer") {
+                            true => options.accept_integer = Some(nested.path.span()),
+                            false => errors.push(nested.error("unsupported Deserialize option")),
+                        }
+
+                        Ok(())
+                    })?;
+                }
+
+                return Ok(());
+            }
+
+            if meta.path.is_ident("JsonSchema") {
+                options.json_schema = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("serde") {
+                let format: Ident = meta.value()?.parse()?;
+
+                match format.to_string().as_str() {
+                    "string" => options.serde = SerdeFormat::String,
+                    "compact" => options.serde = SerdeFormat::Compact,
+                    _ => errors.push(syn::Error::new(
+                        format.span(),
+                        "unsupported serde format. expected `string` or `compact`",
+                    )),
+                }
+
+                return Ok(());
+            }
+
+            if meta.path.is_ident("sortable") {
+                options.sortable = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("sqlx") {
+                options.sqlx = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("postgres") {
+                options.postgres = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("diesel") {
+                options.diesel = parse_sql_storage(&meta, errors)?;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("rusqlite") {
+                options.rusqlite = parse_sql_storage(&meta, errors)?;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("checksum") {
+                options.checksum = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("prefix") {
+                let prefix: syn::LitStr = meta.value()?.parse()?;
+
+                match prefix.value() {
+                    p if p.is_empty() || !p.is_ascii() => errors.push(syn::Error::new(
+                        prefix.span(),
+                        "prefix must be a non-empty ASCII string",
+                    )),
+                    p => options.prefix = Some(p),
+                }
+
+                return Ok(());
+            }
+
+            if meta.path.is_ident("inner") {
+                options.inner = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+
+            if meta.path.is_ident("alphabet") {
+                let alphabet = match meta.value()?.parse()? {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(s), ..
+                    }) => quote_spanned! {s.span()=> ::base64id::Alphabet::new(#s)},
+                    expr => quote! {#expr},
+                };
+
+                options.alphabet = Some(alphabet);
+                return Ok(());
+            }
+
+            if meta.path.is_ident("obfuscate") {
+                let key: Expr = meta.value()?.parse()?;
+                options.obfuscate = Some(quote! {#key});
+                return Ok(());
+            }
+
+            errors.push(meta.error("unsupported base64id attribute"));
+
+            // skip the value of an unsupported key, so that the following keys are still checked
+            if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            }
+
+            Ok(())
+        });
+
+        if let Err(e) = result {
+            errors.push(e);
+        }
+    }
+
+    options
+}
+
+/// Enable the following syntax:
+/// ```ignore
+/// #[derive(base64id::Base64Id)]
+/// #[base64id(Serialize)]
+/// struct MyType(i64);
+/// ```
+fn apply_serialize_trait(
+    ident: &proc_macro2::Ident,
+    inner_type: &InnerType,
+    options: &Options,
+    implementation: &mut proc_macro2::TokenStream,
+) {
+    let compact = match (inner_type, &options.obfuscate) {
+        (InnerType::Integer(_) | InnerType::NonZero(_), None) => {
+            quote! {::serde::Serialize::serialize(&self.0, serializer)}
+        }
+        (InnerType::Integer(t), Some(key)) => {
+            let obfuscate = Ident::new(&format!("obfuscate_{t}"), Span::call_site());
+            quote! {::serde::Serialize::serialize(&::base64id::Key::#obfuscate(&(#key), self.0), serializer)}
+        }
+        (InnerType::NonZero(t), Some(key)) => {
+            let obfuscate = Ident::new(&format!("obfuscate_{t}"), Span::call_site());
+            quote! {::serde::Serialize::serialize(&::base64id::Key::#obfuscate(&(#key), self.0.get()), serializer)}
+        }
+        (InnerType::Bytes(_), _) => quote! {serializer.serialize_bytes(&self.0)},
+    };
+
+    let body = match options.serde {
+        SerdeFormat::Auto => quote! {
+            match serializer.is_human_readable() {
+                true => serializer.collect_str(self),
+                false => #compact,
+            }
+        },
+        SerdeFormat::String => quote! {serializer.collect_str(self)},
+        SerdeFormat::Compact => compact,
+    };
+
+    implementation.extend(quote!(
         impl ::serde::Serialize for #ident {
             fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
                 where
                     S: ::serde::Serializer
             {
-                serializer.collect_str(self)
+                #body
             }
         }
     ));
@@ -324,7 +1216,9 @@ fn apply_serialize_trait(
 /// ```
 fn apply_deserialize_trait(
     ident: &proc_macro2::Ident,
+    inner_type: &InnerType,
     char_len: usize,
+    options: &Options,
     implementation: &mut proc_macro2::TokenStream,
 ) {
     let visitor = Ident::new(
@@ -332,10 +1226,85 @@ fn apply_deserialize_trait(
         Span::call_site(),
     );
 
-    let last_char_range = match char_len {
-        11 | 3 => "AEIMQUYcgkosw048",
-        6 | 22 => "AQgw",
-        _ => panic!("unexpected character length {char_len}. cannot get last_char_range"),
+    // the sortable key selects an alphabet other than base64url, just as the alphabet key does
+    let alphabet_name = match options.alphabet.is_some() || options.sortable {
+        true => "base64",
+        false => "base64url",
+    };
+
+    let (length_expected, prefix_arm) = match options.prefix.as_deref() {
+        Some(prefix) => {
+            let prefix_expected = format!("an id starting with `{prefix}`");
+
+            (
+                format!(
+                    "exactly {char_len} characters, starting with `{prefix}` followed by {} {alphabet_name} characters",
+                    char_len - prefix.len()
+                ),
+                quote! {
+                    ::base64id::Error::InvalidPrefix { .. } => E::invalid_value(
+                        unexpected,
+                        &#prefix_expected,
+                    ),
+                },
+            )
+        }
+        None => (
+            format!("exactly {char_len} {alphabet_name} characters"),
+            quote! {},
+        ),
+    };
+
+    let expecting = format!("a {ident} encoded as {length_expected}");
+
+    let deserialize_compact = match inner_type {
+        InnerType::Integer(t) | InnerType::NonZero(t) => {
+            let method = Ident::new(&format!("deserialize_{t}"), Span::call_site());
+            quote! {deserializer.#method(#visitor)}
+        }
+        InnerType::Bytes(_) => quote! {deserializer.deserialize_bytes(#visitor)},
+    };
+
+    // integers are given to the visitor by formats which describe their own data, such as JSON
+    let (deserialize_string, expecting) = match options.accept_integer {
+        Some(_) => (
+            quote! {deserializer.deserialize_any(#visitor)},
+            format!("{expecting} or an integer"),
+        ),
+        None => (quote! {deserializer.deserialize_str(#visitor)}, expecting),
+    };
+
+    let body = match options.serde {
+        SerdeFormat::Auto => quote! {
+            match deserializer.is_human_readable() {
+                true => #deserialize_string,
+                false => #deserialize_compact,
+            }
+        },
+        SerdeFormat::String => deserialize_string,
+        SerdeFormat::Compact => deserialize_compact,
+    };
+
+    let visit_compact = match (&options.serde, options.accept_integer) {
+        (SerdeFormat::String, None) => None,
+        _ => Some(visit_compact_methods(
+            ident,
+            inner_type,
+            options.obfuscate.as_ref(),
+        )),
+    };
+
+    // compact formats give byte arrays as raw bytes, which are always shorter than the encoded id
+    let visit_raw_bytes = match (&visit_compact, inner_type) {
+        (Some(_), InnerType::Bytes(n)) => {
+            let n = Literal::usize_unsuffixed(*n);
+            quote! {
+                if let Ok(bytes) = <[u8; #n]>::try_from(v) {
+                    return Ok(#ident(bytes));
+                }
+            }
+        }
+        _ => quote! {},
     };
 
     implementation.extend(quote!(
@@ -344,7 +1313,7 @@ fn apply_deserialize_trait(
             where
                 D: ::serde::Deserializer<'de>,
             {
-                deserializer.deserialize_str(#visitor)
+                #body
             }
         }
 
@@ -355,7 +1324,7 @@ fn apply_deserialize_trait(
             type Value = #ident;
 
             fn expecting(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                f.write_str("a base64url encoded string")
+                f.write_str(#expecting)
             }
 
             fn visit_str<E>(self, v: &str) -> ::core::result::Result<Self::Value, E>
@@ -364,59 +1333,916 @@ fn apply_deserialize_trait(
             {
                 use ::core::str::FromStr;
 
-                const EXP1: &str = concat!("exactly ", #char_len, " base64url characters");
-                const EXP2: &str = concat!(
-                    "the last character must be one of the following: ",
-                    #last_char_range
-                );
-                const ERR: &str = concat!("unknown error! expected exactly ", #char_len, "base64url characters");
-
-                #ident::from_str(v).map_err(|e| match e {
-                    ::base64id::Error::InvalidLength => E::invalid_length(v.len(), &EXP1),
-                    ::base64id::Error::InvalidCharacter => E::invalid_value(
-                        ::serde::de::Unexpected::Other("1 or more non-base64url characters"),
-                        &EXP1,
+                #ident::from_str(v).map_err(|e| Self::base64id_error(e, ::serde::de::Unexpected::Str(v)))
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> ::core::result::Result<Self::Value, E>
+            where
+                E: ::serde::de::Error,
+            {
+                #visit_raw_bytes
+
+                match #ident::base64id_decode(v) {
+                    Ok(value) => Ok(#ident(value)),
+                    Err(e) => Err(Self::base64id_error(e, ::serde::de::Unexpected::Bytes(v))),
+                }
+            }
+
+            #visit_compact
+        }
+
+        impl #visitor {
+            /// Describe why the encoded id `unexpected` failed to decode
+            fn base64id_error<E>(e: ::base64id::Error, unexpected: ::serde::de::Unexpected) -> E
+            where
+                E: ::serde::de::Error,
+            {
+                const EXP: &str = #length_expected;
+
+                match e {
+                    ::base64id::Error::InvalidLength { actual, .. } => E::invalid_length(actual, &EXP),
+                    ::base64id::Error::InvalidCharacter { index, character } => E::custom(format_args!(
+                        "invalid value: character `{}` at index {}, expected {}",
+                        character, index, EXP
+                    )),
+                    ::base64id::Error::OutOfBoundsCharacter { index, character, allowed } => E::custom(format_args!(
+                        "invalid value: character `{}` at index {}, the last character must be one of the following: {}",
+                        character, index, allowed
+                    )),
+                    ::base64id::Error::ZeroValue => E::invalid_value(
+                        ::serde::de::Unexpected::Other("an id of zero"),
+                        &"a non-zero id",
                     ),
-                    ::base64id::Error::OutOfBoundsCharacter => E::invalid_value(
-                        ::serde::de::Unexpected::Other("the last character was out of bounds"),
-                        &EXP2,
+                    ::base64id::Error::ChecksumMismatch => E::invalid_value(
+                        unexpected,
+                        &"an id with a matching check character",
                     ),
-                    _ => E::custom(ERR)
+                    #prefix_arm
+                    e => E::custom(e),
+                }
+            }
+        }
+    ));
+}
+
+/// Visitor methods accepting the inner value, as serialized by compact serde formats or accepted with `accept_integer`
+///
+/// Integers are range checked against the inner type, and sequences of bytes must be of the exact length.
+/// Raw byte arrays are accepted by `visit_bytes` instead, alongside the encoded id.
+/// Integers of obfuscated ids are deobfuscated, as they are serialized obfuscated.
+fn visit_compact_methods(
+    ident: &proc_macro2::Ident,
+    inner_type: &InnerType,
+    obfuscate: Option<&proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let (t, from_inner) = match inner_type {
+        InnerType::Integer(t) => (t, quote! {Ok(#ident(v))}),
+        InnerType::NonZero(t) => (
+            t,
+            quote! {
+                #ident::try_from(v).map_err(|_| {
+                    E::invalid_value(
+                        ::serde::de::Unexpected::Other("an id of zero"),
+                        &"a non-zero id",
+                    )
                 })
+            },
+        ),
+        InnerType::Bytes(n) => {
+            let n = Literal::usize_unsuffixed(*n);
+
+            return quote! {
+                fn visit_seq<A>(self, mut seq: A) -> ::core::result::Result<Self::Value, A::Error>
+                where
+                    A: ::serde::de::SeqAccess<'de>,
+                {
+                    use ::serde::de::Error;
+
+                    let mut bytes = [0u8; #n];
+
+                    for (i, byte) in bytes.iter_mut().enumerate() {
+                        match seq.next_element()? {
+                            Some(b) => *byte = b,
+                            None => return Err(A::Error::invalid_length(i, &self)),
+                        }
+                    }
+
+                    match seq.next_element::<u8>()? {
+                        Some(_) => Err(A::Error::invalid_length(#n + 1, &self)),
+                        None => Ok(#ident(bytes)),
+                    }
+                }
+            };
+        }
+    };
+
+    let deobfuscate = obfuscate.map(|key| {
+        let deobfuscate = Ident::new(&format!("deobfuscate_{t}"), Span::call_site());
+        quote! {let v = ::base64id::Key::#deobfuscate(&(#key), v);}
+    });
+
+    // serde has no unexpected value for 128 bit integers
+    let methods = [
+        (
+            quote! {visit_i64},
+            quote! {i64},
+            quote! {::serde::de::Unexpected::Signed(v)},
+        ),
+        (
+            quote! {visit_u64},
+            quote! {u64},
+            quote! {::serde::de::Unexpected::Unsigned(v)},
+        ),
+        (
+            quote! {visit_i128},
+            quote! {i128},
+            quote! {::serde::de::Unexpected::Other("a 128 bit integer")},
+        ),
+        (
+            quote! {visit_u128},
+            quote! {u128},
+            quote! {::serde::de::Unexpected::Other("a 128 bit integer")},
+        ),
+    ];
+
+    methods
+        .into_iter()
+        .map(|(method, int_type, unexpected)| {
+            quote! {
+                fn #method<E>(self, v: #int_type) -> ::core::result::Result<Self::Value, E>
+                where
+                    E: ::serde::de::Error,
+                {
+                    match #t::try_from(v) {
+                        Ok(v) => {
+                            #deobfuscate
+                            #from_inner
+                        }
+                        Err(_) => Err(E::invalid_value(#unexpected, &self)),
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+/// Enable the following syntax:
+/// ```ignore
+/// #[derive(base64id::Base64Id)]
+/// #[base64id(JsonSchema)]
+/// struct MyType(i64);
+/// ```
+///
+/// The schema describes the encoded string, unless the `serde = compact` key selects the inner value.
+fn apply_json_schema_trait(
+    ident: &proc_macro2::Ident,
+    inner_type: &InnerType,
+    struct_inner_type: &proc_macro2::TokenStream,
+    payload_len: usize,
+    char_len: usize,
+    options: &Options,
+    implementation: &mut proc_macro2::TokenStream,
+) {
+    let name = ident.to_string();
+
+    // the generator is only needed to describe the inner type
+    let generator = match (&options.serde, options.accept_integer) {
+        (SerdeFormat::Compact, _) | (_, Some(_)) => quote! {generator},
+        _ => quote! {_generator},
+    };
+
+    let json_schema = match options.serde {
+        SerdeFormat::Compact => quote! {
+            <#struct_inner_type as ::schemars::JsonSchema>::json_schema(#generator)
+        },
+        SerdeFormat::Auto | SerdeFormat::String => {
+            // ids deserialized with accept_integer may also be given as the inner integer
+            let accept_integer = match options.accept_integer {
+                Some(_) => quote! {
+                    ::schemars::json_schema!({
+                        "anyOf": [string, generator.subschema_for::<#struct_inner_type>()],
+                    })
+                },
+                None => quote! {string},
+            };
+
+            let prefix = options.prefix.as_ref().map(|p| quote! {.with_prefix(#p)});
+            let checksum = options.checksum.then(|| quote! {.with_checksum()});
+
+            // a non-zero id cannot be zero, so its example is the id of one
+            let example = match inner_type {
+                InnerType::Integer(_) => quote! {#ident(0)},
+                InnerType::NonZero(t) => quote! {
+                    #ident(match ::core::num::NonZero::<#t>::new(1) {
+                        Some(v) => v,
+                        None => unreachable!(),
+                    })
+                },
+                InnerType::Bytes(n) => {
+                    let n = Literal::usize_unsuffixed(*n);
+                    quote! {#ident([0; #n])}
+                }
+            };
+
+            quote! {
+                let pattern = ::base64id::base64::Pattern::new(&Self::BASE64ID_ALPHABET, #payload_len)
+                    #prefix
+                    #checksum;
+                let example = #example;
+
+                let string = ::schemars::json_schema!({
+                    "type": "string",
+                    "minLength": #char_len,
+                    "maxLength": #char_len,
+                    "pattern": ::std::string::ToString::to_string(&pattern),
+                    "examples": [::std::string::ToString::to_string(&example)],
+                });
+
+                #accept_integer
+            }
+        }
+    };
+
+    implementation.extend(quote!(
+        impl ::schemars::JsonSchema for #ident {
+            fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(#name)
+            }
+
+            fn schema_id() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(::core::concat!(::core::module_path!(), "::", #name))
+            }
+
+            fn json_schema(#generator: &mut ::schemars::SchemaGenerator) -> ::schemars::Schema {
+                #json_schema
+            }
+        }
+    ));
+}
+
+/// Enable the following syntax:
+/// ```ignore
+/// #[derive(base64id::Base64Id)]
+/// #[base64id(sqlx)]
+/// struct MyType(i64);
+/// ```
+///
+/// Integers of up to 64 bits are stored as the signed integer of the same size, as Postgres lacks unsigned integers.
+/// 128 bit integers and byte arrays are stored as big endian bytes, as no database supported by sqlx has 128 bit integers.
+fn apply_sqlx_traits(
+    ident: &proc_macro2::Ident,
+    inner_type: &InnerType,
+    implementation: &mut proc_macro2::TokenStream,
+) {
+    let (storage, to_storage, from_storage) = match inner_type {
+        InnerType::Integer(t) | InnerType::NonZero(t) if !t.to_string().ends_with("128") => {
+            let storage = match t.to_string().starts_with('i') {
+                true => quote! {#t},
+                false => opposite_sign_type(t),
+            };
+
+            let from_storage = match inner_type {
+                InnerType::NonZero(_) => quote! {Ok(Self::try_from(value)?)},
+                _ => quote! {Ok(Self::from(value))},
+            };
+
+            (
+                storage.clone(),
+                quote! {#storage::from(Self(self.0))},
+                from_storage,
+            )
+        }
+        InnerType::Integer(t) | InnerType::NonZero(t) => {
+            let from_storage = match inner_type {
+                InnerType::NonZero(_) => quote! {Ok(Self::try_from(#t::from_be_bytes(bytes))?)},
+                _ => quote! {Ok(Self::from(#t::from_be_bytes(bytes)))},
+            };
+
+            (
+                quote! {::std::vec::Vec<u8>},
+                quote! {#t::from(Self(self.0)).to_be_bytes().to_vec()},
+                quote! {
+                    let bytes = <[u8; 16]>::try_from(value.as_slice())?;
+                    #from_storage
+                },
+            )
+        }
+        InnerType::Bytes(n) => {
+            let n = Literal::usize_unsuffixed(*n);
+
+            (
+                quote! {::std::vec::Vec<u8>},
+                quote! {self.0.to_vec()},
+                quote! {Ok(Self(<[u8; #n]>::try_from(value.as_slice())?))},
+            )
+        }
+    };
+
+    implementation.extend(quote!(
+        impl<DB: ::sqlx::Database> ::sqlx::Type<DB> for #ident
+        where
+            #storage: ::sqlx::Type<DB>,
+        {
+            fn type_info() -> <DB as ::sqlx::Database>::TypeInfo {
+                <#storage as ::sqlx::Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &<DB as ::sqlx::Database>::TypeInfo) -> bool {
+                <#storage as ::sqlx::Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB: ::sqlx::Database> ::sqlx::Encode<'q, DB> for #ident
+        where
+            #storage: ::sqlx::Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as ::sqlx::Database>::ArgumentBuffer<'q>,
+            ) -> ::core::result::Result<::sqlx::encode::IsNull, ::sqlx::error::BoxDynError> {
+                <#storage as ::sqlx::Encode<'q, DB>>::encode(#to_storage, buf)
+            }
+        }
+
+        impl<'r, DB: ::sqlx::Database> ::sqlx::Decode<'r, DB> for #ident
+        where
+            #storage: ::sqlx::Decode<'r, DB>,
+        {
+            fn decode(
+                value: <DB as ::sqlx::Database>::ValueRef<'r>,
+            ) -> ::core::result::Result<Self, ::sqlx::error::BoxDynError> {
+                let value = <#storage as ::sqlx::Decode<'r, DB>>::decode(value)?;
+                #from_storage
+            }
+        }
+    ));
+}
+
+/// Enable the following syntax:
+/// ```ignore
+/// #[derive(base64id::Base64Id)]
+/// #[base64id(diesel)]
+/// struct MyType(i64);
+///
+/// #[derive(base64id::Base64Id)]
+/// #[base64id(diesel = Text)]
+/// struct MyTextType(i64);
+/// ```
+///
+/// Integers are stored as the signed integer SQL type of the same size, and `Text` stores the encoded string.
+/// Expressions are built from the stored value, so ids may be used within queries on every backend.
+/// `ToSql` borrows signed integers directly, so it is implemented for every backend.
+/// Any other value is converted first, then bound with `Output::set_value` on SQLite,
+/// or serialized through `Output::reborrow` on Postgres and MySQL.
+/// These impls name their backend, so each is only generated when the matching `diesel_*` feature is enabled.
+fn apply_diesel_traits(
+    ident: &proc_macro2::Ident,
+    inner_type: &InnerType,
+    storage: &SqlStorage,
+    implementation: &mut proc_macro2::TokenStream,
+) {
+    let (sql_type, storage_type, to_storage, from_storage) = match (storage, inner_type) {
+        (SqlStorage::Inner(_), InnerType::Integer(t) | InnerType::NonZero(t)) => {
+            let storage_type = match t.to_string().starts_with('i') {
+                true => quote! {#t},
+                false => opposite_sign_type(t),
+            };
+
+            let sql_type = match t.to_string().trim_start_matches(['i', 'u']) {
+                "16" => quote! {::diesel::sql_types::SmallInt},
+                "32" => quote! {::diesel::sql_types::Integer},
+                "64" => quote! {::diesel::sql_types::BigInt},
+                _ => unreachable!("128 bit integers are validated by validate_options"),
+            };
+
+            let from_storage = match inner_type {
+                InnerType::NonZero(_) => quote! {Ok(Self::try_from(value)?)},
+                _ => quote! {Ok(Self::from(value))},
+            };
+
+            (
+                sql_type,
+                storage_type.clone(),
+                quote! {#storage_type::from(#ident(self.0))},
+                from_storage,
+            )
+        }
+        (SqlStorage::Inner(_), InnerType::Bytes(_)) => {
+            unreachable!("byte arrays are validated by validate_options")
+        }
+        (SqlStorage::Text, _) => (
+            quote! {::diesel::sql_types::Text},
+            quote! {::std::string::String},
+            quote! {::std::string::ToString::to_string(&self)},
+            quote! {Ok(value.parse()?)},
+        ),
+    };
+
+    let to_sql = match inner_type {
+        InnerType::Integer(t)
+            if t.to_string().starts_with('i') && !matches!(storage, SqlStorage::Text) =>
+        {
+            quote! {
+                impl<DB: ::diesel::backend::Backend> ::diesel::serialize::ToSql<#sql_type, DB> for #ident
+                where
+                    #storage_type: ::diesel::serialize::ToSql<#sql_type, DB>,
+                {
+                    fn to_sql<'b>(
+                        &'b self,
+                        out: &mut ::diesel::serialize::Output<'b, '_, DB>,
+                    ) -> ::diesel::serialize::Result {
+                        <#storage_type as ::diesel::serialize::ToSql<#sql_type, DB>>::to_sql(&self.0, out)
+                    }
+                }
+            }
+        }
+        _ => {
+            // SQLite binds small integers as 32 bit integers
+            let bind_value = match storage_type.to_string().as_str() {
+                "i16" => quote! {i32::from(#to_storage)},
+                _ => to_storage.clone(),
+            };
+
+            // backends are only named when enabled, as naming one requires the diesel feature of that backend
+            let sqlite = cfg!(feature = "diesel_sqlite").then(|| {
+                quote! {
+                    impl ::diesel::serialize::ToSql<#sql_type, ::diesel::sqlite::Sqlite> for #ident {
+                        fn to_sql<'b>(
+                            &'b self,
+                            out: &mut ::diesel::serialize::Output<'b, '_, ::diesel::sqlite::Sqlite>,
+                        ) -> ::diesel::serialize::Result {
+                            out.set_value(#bind_value);
+                            Ok(::diesel::serialize::IsNull::No)
+                        }
+                    }
+                }
+            });
+
+            // backends serializing into bytes only borrow the converted value within to_sql
+            let bytes_backends = [
+                (cfg!(feature = "diesel_postgres"), quote! {::diesel::pg::Pg}),
+                (
+                    cfg!(feature = "diesel_mysql"),
+                    quote! {::diesel::mysql::Mysql},
+                ),
+            ];
+
+            let bytes_backends = bytes_backends
+                .into_iter()
+                .filter(|(enabled, _)| *enabled)
+                .map(|(_, backend)| {
+                    quote! {
+                        impl ::diesel::serialize::ToSql<#sql_type, #backend> for #ident {
+                            fn to_sql<'b>(
+                                &'b self,
+                                out: &mut ::diesel::serialize::Output<'b, '_, #backend>,
+                            ) -> ::diesel::serialize::Result {
+                                let value = #to_storage;
+                                <#storage_type as ::diesel::serialize::ToSql<#sql_type, #backend>>::to_sql(
+                                    &value,
+                                    &mut out.reborrow(),
+                                )
+                            }
+                        }
+                    }
+                });
+
+            quote! {
+                #sqlite
+                #(#bytes_backends)*
+            }
+        }
+    };
+
+    for expression_type in [
+        quote! {#sql_type},
+        quote! {::diesel::sql_types::Nullable<#sql_type>},
+    ] {
+        implementation.extend(quote!(
+            impl ::diesel::expression::AsExpression<#expression_type> for #ident {
+                type Expression = <#storage_type as ::diesel::expression::AsExpression<#expression_type>>::Expression;
+
+                fn as_expression(self) -> Self::Expression {
+                    ::diesel::expression::AsExpression::<#expression_type>::as_expression(#to_storage)
+                }
+            }
+
+            impl<'a> ::diesel::expression::AsExpression<#expression_type> for &'a #ident {
+                type Expression = <#storage_type as ::diesel::expression::AsExpression<#expression_type>>::Expression;
+
+                fn as_expression(self) -> Self::Expression {
+                    ::diesel::expression::AsExpression::<#expression_type>::as_expression(#to_storage)
+                }
+            }
+        ));
+    }
+
+    implementation.extend(quote!(
+        #to_sql
+
+        impl<DB: ::diesel::backend::Backend> ::diesel::deserialize::FromSql<#sql_type, DB> for #ident
+        where
+            #storage_type: ::diesel::deserialize::FromSql<#sql_type, DB>,
+        {
+            fn from_sql(
+                bytes: <DB as ::diesel::backend::Backend>::RawValue<'_>,
+            ) -> ::diesel::deserialize::Result<Self> {
+                let value = <#storage_type as ::diesel::deserialize::FromSql<#sql_type, DB>>::from_sql(bytes)?;
+                #from_storage
+            }
+        }
+
+        impl<DB, ST> ::diesel::deserialize::Queryable<ST, DB> for #ident
+        where
+            DB: ::diesel::backend::Backend,
+            ST: ::diesel::sql_types::SingleValue,
+            Self: ::diesel::deserialize::FromSql<ST, DB>,
+        {
+            type Row = Self;
+
+            fn build(row: Self) -> ::diesel::deserialize::Result<Self> {
+                Ok(row)
+            }
+        }
+    ));
+}
+
+/// Enable the following syntax:
+/// ```ignore
+/// #[derive(base64id::Base64Id)]
+/// #[base64id(rusqlite)]
+/// struct MyType(i64);
+///
+/// #[derive(base64id::Base64Id)]
+/// #[base64id(rusqlite = Text)]
+/// struct MyTextType(i64);
+/// ```
+///
+/// Integers of up to 64 bits are stored as `INTEGER`, as the signed integer of the same size.
+/// 128 bit integers are stored as a 16 byte big endian `BLOB`, as SQLite integers are at most 64 bits, and byte arrays are stored as a `BLOB`.
+/// `Text` stores the encoded string, and decoding errors are returned within `FromSqlError::Other`.
+fn apply_rusqlite_traits(
+    ident: &proc_macro2::Ident,
+    inner_type: &InnerType,
+    storage: &SqlStorage,
+    implementation: &mut proc_macro2::TokenStream,
+) {
+    let other_error = quote! {::rusqlite::types::FromSqlError::Other(::std::boxed::Box::new(e))};
+
+    let (to_sql, from_sql) = match (storage, inner_type) {
+        (SqlStorage::Inner(_), InnerType::Integer(t) | InnerType::NonZero(t))
+            if !t.to_string().ends_with("128") =>
+        {
+            let storage_type = match t.to_string().starts_with('i') {
+                true => quote! {#t},
+                false => opposite_sign_type(t),
+            };
+
+            let from_storage = match inner_type {
+                InnerType::NonZero(_) => quote! {Self::try_from(value).map_err(|e| #other_error)},
+                _ => quote! {Ok(Self::from(value))},
+            };
+
+            (
+                quote! {::rusqlite::types::ToSqlOutput::from(#storage_type::from(Self(self.0)))},
+                quote! {
+                    let value = <#storage_type as ::rusqlite::types::FromSql>::column_result(value)?;
+                    #from_storage
+                },
+            )
+        }
+        (SqlStorage::Inner(_), InnerType::Integer(t) | InnerType::NonZero(t)) => {
+            let from_storage = match inner_type {
+                InnerType::NonZero(_) => {
+                    quote! {Self::try_from(#t::from_be_bytes(bytes)).map_err(|e| #other_error)}
+                }
+                _ => quote! {Ok(Self::from(#t::from_be_bytes(bytes)))},
+            };
+
+            (
+                quote! {::rusqlite::types::ToSqlOutput::from(#t::from(Self(self.0)).to_be_bytes().to_vec())},
+                quote! {
+                    let bytes = <[u8; 16] as ::rusqlite::types::FromSql>::column_result(value)?;
+                    #from_storage
+                },
+            )
+        }
+        (SqlStorage::Inner(_), InnerType::Bytes(_)) => (
+            quote! {::rusqlite::types::ToSqlOutput::Borrowed(::rusqlite::types::ValueRef::Blob(&self.0))},
+            quote! {Ok(Self(::rusqlite::types::FromSql::column_result(value)?))},
+        ),
+        (SqlStorage::Text, _) => (
+            quote! {::rusqlite::types::ToSqlOutput::from(::std::string::ToString::to_string(self))},
+            quote! {value.as_str()?.parse().map_err(|e| #other_error)},
+        ),
+    };
+
+    implementation.extend(quote!(
+        impl ::rusqlite::types::ToSql for #ident {
+            fn to_sql(&self) -> ::rusqlite::Result<::rusqlite::types::ToSqlOutput<'_>> {
+                Ok(#to_sql)
+            }
+        }
+
+        impl ::rusqlite::types::FromSql for #ident {
+            fn column_result(
+                value: ::rusqlite::types::ValueRef<'_>,
+            ) -> ::rusqlite::types::FromSqlResult<Self> {
+                #from_sql
             }
         }
     ));
 }
 
-/// Ensure data type is a tuple struct and contains one of the expected integer types inside
-fn get_validated_struct_data(data: syn::Data) -> syn::Ident {
+/// Enable the following syntax:
+/// ```ignore
+/// #[derive(base64id::Base64Id)]
+/// #[base64id(postgres)]
+/// struct MyType(i64);
+/// ```
+///
+/// Integers of up to 64 bits are stored as the `int2`, `int4` or `int8` of the same size, using the signed conversions.
+/// 128 bit integers and byte arrays of 16 bytes are stored as big endian bytes within a `uuid` or `bytea`, and other byte arrays within a `bytea`.
+fn apply_postgres_traits(
+    ident: &proc_macro2::Ident,
+    inner_type: &InnerType,
+    implementation: &mut proc_macro2::TokenStream,
+) {
+    let accepts_bytes = |len: usize| match len {
+        16 => quote! {
+            *ty == ::postgres_types::Type::UUID || *ty == ::postgres_types::Type::BYTEA
+        },
+        _ => quote! {*ty == ::postgres_types::Type::BYTEA},
+    };
+
+    let (accepts, to_sql, from_sql) = match inner_type {
+        InnerType::Integer(t) | InnerType::NonZero(t) if !t.to_string().ends_with("128") => {
+            let storage = match t.to_string().starts_with('i') {
+                true => quote! {#t},
+                false => opposite_sign_type(t),
+            };
+
+            let from_storage = match inner_type {
+                InnerType::NonZero(_) => quote! {Ok(Self::try_from(value)?)},
+                _ => quote! {Ok(Self::from(value))},
+            };
+
+            (
+                quote! {<#storage as ::postgres_types::ToSql>::accepts(ty)},
+                quote! {
+                    <#storage as ::postgres_types::ToSql>::to_sql(&#storage::from(Self(self.0)), ty, out)
+                },
+                quote! {
+                    let value = <#storage as ::postgres_types::FromSql<'a>>::from_sql(ty, raw)?;
+                    #from_storage
+                },
+            )
+        }
+        InnerType::Integer(t) | InnerType::NonZero(t) => {
+            let from_storage = match inner_type {
+                InnerType::NonZero(_) => quote! {Ok(Self::try_from(#t::from_be_bytes(bytes))?)},
+                _ => quote! {Ok(Self::from(#t::from_be_bytes(bytes)))},
+            };
+
+            (
+                accepts_bytes(16),
+                quote! {
+                    out.extend_from_slice(&#t::from(Self(self.0)).to_be_bytes());
+                    Ok(::postgres_types::IsNull::No)
+                },
+                quote! {
+                    let bytes = <[u8; 16]>::try_from(raw)?;
+                    #from_storage
+                },
+            )
+        }
+        InnerType::Bytes(n) => {
+            let accepts = accepts_bytes(*n);
+            let n = Literal::usize_unsuffixed(*n);
+
+            (
+                accepts,
+                quote! {
+                    out.extend_from_slice(&self.0);
+                    Ok(::postgres_types::IsNull::No)
+                },
+                quote! {Ok(Self(<[u8; #n]>::try_from(raw)?))},
+            )
+        }
+    };
+
+    // to_sql_checked! names BytesMut through the same path
+    implementation.extend(quote!(
+        impl ::postgres_types::ToSql for #ident {
+            fn to_sql(
+                &self,
+                ty: &::postgres_types::Type,
+                out: &mut ::postgres_types::private::BytesMut,
+            ) -> ::core::result::Result<
+                ::postgres_types::IsNull,
+                ::std::boxed::Box<dyn ::std::error::Error + ::core::marker::Sync + ::core::marker::Send>,
+            > {
+                #to_sql
+            }
+
+            fn accepts(ty: &::postgres_types::Type) -> bool {
+                #accepts
+            }
+
+            ::postgres_types::to_sql_checked!();
+        }
+
+        impl<'a> ::postgres_types::FromSql<'a> for #ident {
+            fn from_sql(
+                ty: &::postgres_types::Type,
+                raw: &'a [u8],
+            ) -> ::core::result::Result<
+                Self,
+                ::std::boxed::Box<dyn ::std::error::Error + ::core::marker::Sync + ::core::marker::Send>,
+            > {
+                #from_sql
+            }
+
+            fn accepts(ty: &::postgres_types::Type) -> bool {
+                #accepts
+            }
+        }
+    ));
+}
+
+/// The type within a base64id tuple struct
+enum InnerType {
+    /// One of the supported integer types
+    Integer(Ident),
+    /// A `NonZero` wrapper around one of the supported integer types
+    NonZero(Ident),
+    /// A byte array of the given length
+    Bytes(usize),
+}
+
+/// Ensure data type is a tuple struct with exactly 1 field, returning the type of the field
+fn get_validated_struct_field(data: syn::Data) -> syn::Result<syn::Type> {
+    const ERROR_NOT_TUPLE_STRUCT: &str = "unsupported data type. expected a tuple struct";
+
     let data = match data {
         syn::Data::Struct(s) => s,
-        _ => panic!("unsupported data type. expected a tuple struct"),
+        syn::Data::Enum(e) => {
+            return Err(syn::Error::new(e.enum_token.span, ERROR_NOT_TUPLE_STRUCT))
+        }
+        syn::Data::Union(u) => {
+            return Err(syn::Error::new(u.union_token.span, ERROR_NOT_TUPLE_STRUCT))
+        }
     };
 
     let fields = match data.fields {
-        syn::Fields::Unnamed(f) => f.unnamed,
-        _ => panic!("unsupported data type. expected a tuple struct"),
+        syn::Fields::Unnamed(f) => f,
+        syn::Fields::Named(f) => return Err(syn::Error::new_spanned(f, ERROR_NOT_TUPLE_STRUCT)),
+        syn::Fields::Unit => {
+            return Err(syn::Error::new(
+                data.struct_token.span,
+                ERROR_NOT_TUPLE_STRUCT,
+            ))
+        }
     };
 
-    let item = match fields.len() {
-        1 => fields.first().unwrap(),
-        _ => panic!("expected a tuple struct with exactly 1 field"),
+    match fields.unnamed.len() {
+        1 => Ok(fields.unnamed[0].ty.clone()),
+        _ => Err(syn::Error::new_spanned(
+            fields,
+            "expected a tuple struct with exactly 1 field",
+        )),
+    }
+}
+
+/// Ensure a type is one of the expected integer types, their `NonZero` equivalents, or a byte array
+///
+/// If `alias_hint` is set, the error for an unknown path type suggests the `inner` key.
+fn get_validated_inner_type(ty: &syn::Type, alias_hint: bool) -> syn::Result<InnerType> {
+    let path = match ty {
+        syn::Type::Path(p) if p.qself.is_none() => &p.path,
+        syn::Type::Array(a) => return Ok(InnerType::Bytes(get_validated_array_len(a)?)),
+        _ => return Err(syn::Error::new_spanned(ty, ERROR_INVALID_INNER_TYPE)),
     };
 
-    let item_path = match item.ty.clone() {
-        syn::Type::Path(p) => p.path,
-        _ => panic!("{ERROR_INVALID_INNER_TYPE}"),
+    if let Some(t) = get_validated_nonzero_type(path)? {
+        return Ok(InnerType::NonZero(t));
+    }
+
+    match get_primitive_ident(path) {
+        Some(t) if is_integer_type(t) => Ok(InnerType::Integer(t.clone())),
+        // primitive types can't be aliases
+        Some(t) if is_primitive_type(t) => Err(syn::Error::new_spanned(ty, ERROR_INVALID_INNER_TYPE)),
+        _ if alias_hint => Err(syn::Error::new_spanned(
+            ty,
+            format!("{ERROR_INVALID_INNER_TYPE}. a type alias requires #[base64id(inner = T)], where T is the aliased type"),
+        )),
+        _ => Err(syn::Error::new_spanned(ty, ERROR_INVALID_INNER_TYPE)),
+    }
+}
+
+/// Get the primitive type named by a path, such as `u64`, `core::primitive::u64` or `::std::primitive::u64`
+fn get_primitive_ident(path: &syn::Path) -> Option<&Ident> {
+    let segments: Vec<&syn::PathSegment> = path.segments.iter().collect();
+
+    if segments.iter().any(|s| !s.arguments.is_none()) {
+        return None;
+    }
+
+    match segments.as_slice() {
+        [t] if path.leading_colon.is_none() => Some(&t.ident),
+        [krate, module, t]
+            if (krate.ident == "core" || krate.ident == "std") && module.ident == "primitive" =>
+        {
+            Some(&t.ident)
+        }
+        _ => None,
+    }
+}
+
+fn is_primitive_type(ident: &Ident) -> bool {
+    is_integer_type(ident)
+        || matches!(
+            ident.to_string().as_str(),
+            "i8" | "u8" | "isize" | "usize" | "f32" | "f64" | "bool" | "char" | "str"
+        )
+}
+
+fn is_integer_type(ident: &Ident) -> bool {
+    matches!(
+        ident.to_string().as_str(),
+        "i128" | "i64" | "i32" | "i16" | "u128" | "u64" | "u32" | "u16"
+    )
+}
+
+/// Get the integer type within a `NonZero` type path, such as `NonZeroU64`, `core::num::NonZeroU64` or `NonZero<u64>`
+///
+/// Returns `None` if `path` is not a `NonZero` type.
+fn get_validated_nonzero_type(path: &syn::Path) -> syn::Result<Option<Ident>> {
+    let invalid_type = || syn::Error::new_spanned(path, ERROR_INVALID_INNER_TYPE);
+
+    let segments: Vec<&syn::PathSegment> = path.segments.iter().collect();
+    let Some((last, prefix)) = segments.split_last() else {
+        return Ok(None);
+    };
+    let prefix: Vec<String> = prefix.iter().map(|s| s.ident.to_string()).collect();
+
+    match prefix
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [] | ["core" | "std", "num"] => (),
+        _ => return Ok(None),
+    }
+
+    let integer_type = match (last.ident.to_string().as_str(), &last.arguments) {
+        ("NonZero", syn::PathArguments::AngleBracketed(args)) => match args.args.first() {
+            Some(syn::GenericArgument::Type(syn::Type::Path(p))) if args.args.len() == 1 => {
+                match get_primitive_ident(&p.path) {
+                    Some(t) => t.clone(),
+                    None => return Err(invalid_type()),
+                }
+            }
+            _ => return Err(invalid_type()),
+        },
+        (name, syn::PathArguments::None) => match name.strip_prefix("NonZero") {
+            Some(integer_type) => Ident::new(&integer_type.to_lowercase(), last.ident.span()),
+            None => return Ok(None),
+        },
+        _ => return Ok(None),
     };
 
-    let item_type = match item_path.get_ident() {
-        Some(t) => t,
-        None => panic!("{ERROR_INVALID_INNER_TYPE}"),
+    match is_integer_type(&integer_type) {
+        true => Ok(Some(integer_type)),
+        false => Err(invalid_type()),
+    }
+}
+
+/// Ensure an array type is `[u8; N]`, where `N` is a non-zero integer literal
+fn get_validated_array_len(array: &syn::TypeArray) -> syn::Result<usize> {
+    match &*array.elem {
+        syn::Type::Path(p) if p.path.is_ident("u8") => (),
+        _ => return Err(syn::Error::new_spanned(array, ERROR_INVALID_INNER_TYPE)),
+    }
+
+    let len = match &array.len {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(len), ..
+        }) => len.base10_parse::<usize>()?,
+        len => {
+            return Err(syn::Error::new_spanned(
+                len,
+                "unsupported array length. expected an integer literal",
+            ))
+        }
     };
 
-    match item_type.to_string().as_str() {
-        "i128" | "i64" | "i32" | "i16" | "u128" | "u64" | "u32" | "u16" => item_type.clone(),
-        _ => panic!("{ERROR_INVALID_INNER_TYPE}"),
+    match len {
+        0 => Err(syn::Error::new_spanned(
+            &array.len,
+            "unsupported array length. expected at least 1 byte",
+        )),
+        len => Ok(len),
     }
 }
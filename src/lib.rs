@@ -1,7 +1,7 @@
 //! This crate allows for fixed length 64, 32 and 16 bit integers to be represented as [base64url](https://datatracker.ietf.org/doc/html/rfc4648#section-5) encoded strings.
 //! This is useful for exchanging unique identifiers in a web based contexts; eg. sending an SQL primary key to a client with as few character as possible.
 //!
-//! This crate is `#![no_std]`.
+//! This crate is `#![no_std]`. The optional `std` feature adds `SystemClock`, for generating ids with a [`Snowflake`] layout using the system time.
 //!
 //! ## Quick Start
 //! Add the following to your `Cargo.toml` file.
@@ -65,9 +65,15 @@
 //! }
 //! ```
 
-#[doc(hidden)]
+/// Encoding and decoding of integers without a Base64Id type, including batches of ids, sortable and lenient decoding
 pub use base64id_core::base64;
 
-pub use base64id_core::Error;
+pub use base64id_core::{
+    AllowedCharacters, Alphabet, Base64Str, Clock, Error, Key, Repairs, Rng, SliceError, Snowflake,
+    SnowflakeGenerator, SnowflakeParts, UlidGenerator, UlidParts,
+};
+
+#[cfg(feature = "std")]
+pub use base64id_core::SystemClock;
 
 pub use base64id_derive::Base64Id;
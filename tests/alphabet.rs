@@ -0,0 +1,92 @@
+use base64id::{Alphabet, Base64Id, Error};
+use core::str::FromStr;
+
+const REVERSED: Alphabet =
+    Alphabet::new("_-9876543210zyxwvutsrqponmlkjihgfedcbaZYXWVUTSRQPONMLKJIHGFEDCBA");
+
+#[derive(Base64Id, Debug)]
+#[base64id(alphabet = Alphabet::STANDARD, Serialize, Deserialize)]
+struct StandardId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(alphabet = REVERSED)]
+struct ReversedId(u32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(alphabet = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz.~")]
+struct LiteralId(u16);
+
+#[test]
+fn standard_encode() {
+    let id = StandardId(3161873750843059683);
+    assert_eq!(format!("{id}"), "K+E7bI+h7eM");
+}
+
+#[test]
+fn standard_decode() {
+    let id = StandardId::from_str("K+E7bI+h7eM").expect("failed to decode id");
+    assert_eq!(id, StandardId(3161873750843059683));
+}
+
+#[test]
+fn standard_rejects_base64url() {
+    let err = StandardId::from_str("K-E7bI-h7eM").expect_err("decoded a base64url id");
+    assert_eq!(
+        err,
+        Error::InvalidCharacter {
+            index: 1,
+            character: '-'
+        }
+    );
+}
+
+#[test]
+fn standard_serde() {
+    let id = StandardId(3161873750843059683);
+    let json = serde_json::to_string(&id).expect("failed to serialize id");
+    assert_eq!(json, "\"K+E7bI+h7eM\"");
+
+    let id: StandardId = serde_json::from_str(&json).expect("failed to deserialize id");
+    assert_eq!(id, StandardId(3161873750843059683));
+}
+
+#[test]
+fn reversed_roundtrip() {
+    let id = ReversedId(0);
+    assert_eq!(format!("{id}"), "______");
+
+    let id = ReversedId(u32::MAX);
+    let string = format!("{id}");
+    assert_eq!(string, "AAAAAP");
+    assert_eq!(ReversedId::from_str(&string), Ok(id));
+}
+
+#[test]
+fn reversed_out_of_bounds() {
+    let err = ReversedId::from_str("AAAAAA").expect_err("decoded an out of bounds id");
+    let Error::OutOfBoundsCharacter {
+        index,
+        character,
+        allowed,
+    } = err
+    else {
+        panic!("unexpected error {err:?}");
+    };
+
+    assert_eq!((index, character), (5, 'A'));
+    assert_eq!(allowed.as_str(), "_vfP");
+}
+
+#[test]
+fn literal_roundtrip() {
+    let id = LiteralId(u16::MAX);
+    let string = format!("{id}");
+    assert_eq!(string, "~~y");
+    assert_eq!(LiteralId::from_str(&string), Ok(id));
+}
+
+#[test]
+fn literal_from_encoded_const() {
+    const ID: LiteralId = LiteralId::from_encoded_const("00G");
+    assert_eq!(ID, LiteralId(4));
+}
@@ -0,0 +1,82 @@
+use base64id::{Base64Id, Error};
+use core::str::FromStr;
+
+#[derive(Base64Id, Debug)]
+#[base64id(Serialize, Deserialize)]
+struct Digest([u8; 32]);
+
+#[derive(Base64Id, Debug)]
+struct ObjectId([u8; 20]);
+
+#[derive(Base64Id, Debug)]
+struct Token([u8; 12]);
+
+#[derive(Base64Id, Debug)]
+#[base64id(sortable)]
+struct SortableKey([u8; 5]);
+
+/// sha256 of an empty input
+const EMPTY_DIGEST: [u8; 32] = [
+    0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8, 0x99, 0x6f, 0xb9, 0x24,
+    0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95, 0x99, 0x1b, 0x78, 0x52, 0xb8, 0x55,
+];
+
+#[test]
+fn digest_roundtrip() {
+    let id = Digest(EMPTY_DIGEST);
+    let encoded = id.encode();
+
+    assert_eq!(encoded, "47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU");
+    assert_eq!(Digest::from_str(&encoded), Ok(id));
+}
+
+#[test]
+fn digest_serde() {
+    let json = serde_json::to_string(&Digest(EMPTY_DIGEST)).expect("failed to serialize id");
+    assert_eq!(json, "\"47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU\"");
+
+    let id: Digest = serde_json::from_str(&json).expect("failed to deserialize id");
+    assert_eq!(id, Digest(EMPTY_DIGEST));
+}
+
+#[test]
+fn object_id_roundtrip() {
+    let bytes: [u8; 20] = core::array::from_fn(|i| i as u8 * 13);
+    let encoded = ObjectId(bytes).to_string();
+
+    assert_eq!(encoded.len(), 27);
+    assert_eq!(ObjectId::from_str(&encoded), Ok(ObjectId(bytes)));
+    assert_eq!(<[u8; 20]>::from(ObjectId(bytes)), bytes);
+}
+
+#[test]
+fn object_id_out_of_bounds() {
+    let err = ObjectId::from_str("AAAAAAAAAAAAAAAAAAAAAAAAAAB").expect_err("decoded an invalid id");
+    assert!(matches!(
+        err,
+        Error::OutOfBoundsCharacter {
+            index: 26,
+            character: 'B',
+            ..
+        }
+    ));
+}
+
+#[test]
+fn token_without_padding_bits() {
+    const TOKEN: Token = Token::from_encoded_const("________________");
+
+    assert_eq!(TOKEN, Token([0xFF; 12]));
+    assert_eq!(Token::try_from(*b"AAAAAAAAAAAAAAAA"), Ok(Token([0; 12])));
+}
+
+#[test]
+fn sortable_order() {
+    let mut keys = [[0x00; 5], [0xFF; 5], [0x01, 0, 0, 0, 0], [0, 0, 0, 0, 0x01]];
+    let mut encoded = keys.map(|k| SortableKey(k).to_string());
+
+    keys.sort();
+    encoded.sort();
+
+    assert_eq!(encoded, keys.map(|k| SortableKey(k).to_string()));
+}
@@ -0,0 +1,124 @@
+use base64id::{Base64Id, Error, Key, Repairs};
+use core::num::NonZeroU32;
+use core::str::FromStr;
+
+#[derive(Base64Id, Debug)]
+#[base64id(checksum, Serialize, Deserialize)]
+struct MyId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(checksum, sortable)]
+struct MySortableId(u32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(checksum, obfuscate = Key::new(7))]
+struct MyObfuscatedId(NonZeroU32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(checksum)]
+struct MyDigest([u8; 4]);
+
+#[test]
+fn appends_check_character() {
+    assert_eq!(MyId(1).encode(), "AAAAAAAAAAE4");
+    assert_eq!(MyId(3161873750843059683).to_string().len(), 12);
+    assert_eq!(MyId::from_str("AAAAAAAAAAE4"), Ok(MyId(1)));
+}
+
+#[test]
+fn detects_typos() {
+    assert_eq!(MyId::from_str("AAAAAAAAAAF4"), Err(Error::ChecksumMismatch));
+    assert_eq!(MyId::from_str("AAAAAAAAAEA4"), Err(Error::ChecksumMismatch));
+    assert_eq!(MyId::from_str("AAAAAAAAAAE5"), Err(Error::ChecksumMismatch));
+}
+
+#[test]
+fn decode_errors() {
+    assert_eq!(
+        MyId::from_str("AAAAAAAAAAE"),
+        Err(Error::InvalidLength {
+            actual: 11,
+            expected: 12
+        })
+    );
+    assert_eq!(
+        MyId::from_str("AAAAAAAAAA+4"),
+        Err(Error::InvalidCharacter {
+            index: 10,
+            character: '+'
+        })
+    );
+    assert!(matches!(
+        MyId::from_str("AAAAAAAAAAB-"),
+        Err(Error::OutOfBoundsCharacter {
+            index: 10,
+            character: 'B',
+            ..
+        })
+    ));
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(MyId::try_from(*b"AAAAAAAAAAE4"), Ok(MyId(1)));
+    assert_eq!(
+        MyId::try_from(['A', 'A', 'A', 'A', 'A', 'A', 'A', 'A', 'A', 'A', 'E', '4']),
+        Ok(MyId(1))
+    );
+
+    const ID: MyId = MyId::from_encoded_const("AAAAAAAAAAE4");
+    assert_eq!(ID, MyId(1));
+}
+
+#[test]
+#[should_panic(expected = "check character does not match")]
+fn const_checksum_mismatch() {
+    let _ = MyId::from_encoded_const("AAAAAAAAAAE5");
+}
+
+#[test]
+fn lenient() {
+    let (id, applied) = MyId::parse_lenient(" 'AAAAAAAAAAE4' ", Repairs::ALL).unwrap();
+
+    assert_eq!(id, MyId(1));
+    assert_eq!(applied, Repairs::TRIM_WHITESPACE | Repairs::STRIP_QUOTES);
+}
+
+#[test]
+fn combined_keys() {
+    let mut values = [u32::MAX, 0, 7, 1 << 20];
+    let mut encoded = values.map(|v| MySortableId(v).to_string());
+
+    values.sort();
+    encoded.sort();
+
+    assert_eq!(encoded, values.map(|v| MySortableId(v).to_string()));
+    assert_eq!(encoded[0].len(), 7);
+
+    let id = MyObfuscatedId(NonZeroU32::new(42).unwrap());
+    assert_eq!(MyObfuscatedId::from_str(&id.to_string()), Ok(id));
+
+    let digest = MyDigest([0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(MyDigest::from_str(&digest.to_string()), Ok(digest));
+}
+
+#[test]
+fn serde() {
+    let json = serde_json::to_string(&MyId(1)).expect("failed to serialize id");
+    assert_eq!(json, "\"AAAAAAAAAAE4\"");
+
+    let id: MyId = serde_json::from_str(&json).expect("failed to deserialize id");
+    assert_eq!(id, MyId(1));
+
+    let err = serde_json::from_str::<MyId>("\"AAAAAAAAAAE5\"").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: string \"AAAAAAAAAAE5\", expected an id with a matching check character at line 1 column 14"
+    );
+
+    let err = serde_json::from_str::<MyId>("\"AAAAAAAAAAE\"").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 11, expected exactly 12 base64url characters at line 1 column 13"
+    );
+}
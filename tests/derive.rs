@@ -21,6 +21,49 @@ macro_rules! generate_derive_test_suite {
                     .expect("failed to convert str to struct via FromStr trait");
             }
 
+            #[test]
+            fn struct_from_byte_array() {
+                let mut array = [0u8; $struct_str.len()];
+                array.copy_from_slice($struct_str.as_bytes());
+
+                let id = $struct_type::try_from(array)
+                    .expect("failed to convert byte array to struct via TryFrom trait");
+                assert_eq!(id, $struct_type(0));
+            }
+
+            #[test]
+            fn struct_encode() {
+                let id = $struct_type($int_value);
+                let encoded: base64id::Base64Str<{ $struct_str.len() }> = id.encode();
+
+                assert_eq!(encoded, $struct_str);
+                assert_eq!(encoded.as_bytes(), $struct_str.as_bytes());
+            }
+
+            #[test]
+            fn struct_from_encoded_const() {
+                const ID: $struct_type = $struct_type::from_encoded_const($struct_str);
+                assert_eq!(ID, $struct_type($int_value));
+            }
+
+            #[test]
+            #[should_panic(expected = "expected exactly")]
+            fn struct_from_encoded_const_invalid() {
+                let _ = $struct_type::from_encoded_const("A");
+            }
+
+            #[test]
+            #[should_panic(expected = "contains a character outside of the alphabet")]
+            fn struct_from_encoded_const_invalid_character() {
+                let _ = $struct_type::from_encoded_const(&"!".repeat($struct_str.len()));
+            }
+
+            #[test]
+            #[should_panic(expected = "the last character is out of bounds")]
+            fn struct_from_encoded_const_out_of_bounds() {
+                let _ = $struct_type::from_encoded_const(&"_".repeat($struct_str.len()));
+            }
+
             #[test]
             fn int_from_struct() {
                 let int = $int_type::from($struct_type($int_value));
@@ -53,7 +96,13 @@ macro_rules! generate_derive_test_suite {
             #[test]
             fn error_bad_char() {
                 let err = $struct_type::from_str("A").expect_err("failed to get an error");
-                assert_eq!(Error::InvalidLength, err);
+                assert_eq!(
+                    Error::InvalidLength {
+                        actual: 1,
+                        expected: $struct_str.len()
+                    },
+                    err
+                );
             }
 
             #[test]
@@ -73,6 +122,31 @@ macro_rules! generate_derive_test_suite {
                 assert_eq!($struct_type($int_value), id);
             }
 
+            #[test]
+            fn serde_deserialize_errors() {
+                let err = serde_json::from_str::<$struct_type>("\"\u{e9}\u{e9}\"")
+                    .expect_err("deserialized an invalid value");
+                assert!(err.to_string().starts_with(&format!(
+                    "invalid length 2, expected exactly {} base64url characters",
+                    $struct_str.len()
+                )));
+
+                let string = format!("=={}", &$struct_str[2..]);
+                let err = serde_json::from_str::<$struct_type>(&format!("\"{string}\""))
+                    .expect_err("deserialized an invalid value");
+                assert!(err
+                    .to_string()
+                    .starts_with("invalid value: character `=` at index 0"));
+
+                let string = format!("{}B", &$struct_str[1..]);
+                let err = serde_json::from_str::<$struct_type>(&format!("\"{string}\""))
+                    .expect_err("deserialized an invalid value");
+                assert!(err.to_string().starts_with(&format!(
+                    "invalid value: character `B` at index {}, the last character must be one of the following: A",
+                    $struct_str.len() - 1
+                )));
+            }
+
             /// Ensure deserialize impl works without use of external FromStr import
             mod can_deserialize_without_use_from_str {
                 use base64id::Base64Id;
@@ -98,3 +172,26 @@ generate_derive_test_suite!(derive_32_u, MyIdu32, u32, 0u32, i32, 0i32, "AAAAAA"
 
 generate_derive_test_suite!(derive_16_i, MyIdi16, i16, 0i16, u16, 0u16, "AAA");
 generate_derive_test_suite!(derive_16_u, MyIdu16, u16, 0u16, i16, 0i16, "AAA");
+
+mod derive_sortable {
+    use base64id::Base64Id;
+
+    #[derive(Base64Id, Debug)]
+    #[base64id(sortable, Deserialize)]
+    struct MySortableId(i64);
+
+    #[test]
+    fn serde_deserialize_errors() {
+        let err = serde_json::from_str::<MySortableId>("\"--\"")
+            .expect_err("deserialized an invalid value");
+        assert!(err
+            .to_string()
+            .starts_with("invalid length 2, expected exactly 11 base64 characters"));
+
+        let err = serde_json::from_str::<MySortableId>("true")
+            .expect_err("deserialized an invalid value");
+        assert!(err
+            .to_string()
+            .starts_with("invalid type: boolean `true`, expected a MySortableId encoded as exactly 11 base64 characters"));
+    }
+}
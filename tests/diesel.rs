@@ -0,0 +1,259 @@
+use base64id::Base64Id;
+use core::num::NonZeroU32;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Integer, Nullable, SmallInt, Text};
+#[cfg(any(feature = "diesel_postgres", feature = "diesel_mysql"))]
+use diesel::{
+    query_builder::{bind_collector::RawBytesBindCollector, BindCollector},
+    serialize::ToSql,
+    sql_types::HasSqlType,
+};
+
+#[derive(Base64Id, Debug)]
+#[base64id(diesel)]
+struct UserId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(diesel)]
+struct OrderId(u64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(diesel)]
+struct ShortId(u16);
+
+#[derive(Base64Id, Debug)]
+#[base64id(diesel)]
+struct InvoiceId(NonZeroU32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(diesel = Text)]
+struct TraceId(u128);
+
+#[cfg(feature = "diesel_sqlite")]
+type Row = (UserId, Option<OrderId>, Option<ShortId>, Option<TraceId>);
+
+diesel::table! {
+    ids (rowid) {
+        rowid -> BigInt,
+        user_id -> BigInt,
+        order_id -> Nullable<BigInt>,
+        short_id -> Nullable<SmallInt>,
+        trace_id -> Nullable<Text>,
+    }
+}
+
+fn connect() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("failed to open database");
+
+    diesel::sql_query(
+        "CREATE TABLE ids (user_id BIGINT NOT NULL, order_id BIGINT, short_id SMALLINT, trace_id TEXT)",
+    )
+    .execute(&mut conn)
+    .expect("failed to create table");
+
+    conn
+}
+
+#[test]
+fn stores_signed_integer() {
+    let mut conn = connect();
+
+    let (stored, id): (i64, UserId) = diesel::select((
+        UserId(-42).into_sql::<BigInt>(),
+        UserId(-42).into_sql::<BigInt>(),
+    ))
+    .get_result(&mut conn)
+    .unwrap();
+
+    assert_eq!(stored, -42);
+    assert_eq!(id, UserId(-42));
+
+    let id: Option<UserId> = diesel::select(Some(UserId(7)).into_sql::<Nullable<BigInt>>())
+        .get_result(&mut conn)
+        .unwrap();
+
+    assert_eq!(id, Some(UserId(7)));
+}
+
+#[test]
+fn stores_unsigned_as_signed() {
+    let mut conn = connect();
+
+    let (stored, id): (i64, OrderId) = diesel::select((
+        OrderId(u64::MAX).into_sql::<BigInt>(),
+        OrderId(u64::MAX).into_sql::<BigInt>(),
+    ))
+    .get_result(&mut conn)
+    .unwrap();
+
+    assert_eq!(stored, -1);
+    assert_eq!(id, OrderId(u64::MAX));
+
+    let (stored, id): (i16, ShortId) = diesel::select((
+        ShortId(40_000).into_sql::<SmallInt>(),
+        ShortId(40_000).into_sql::<SmallInt>(),
+    ))
+    .get_result(&mut conn)
+    .unwrap();
+
+    assert_eq!(stored, i16::from(ShortId(40_000)));
+    assert_eq!(id, ShortId(40_000));
+}
+
+#[test]
+fn nonzero() {
+    let mut conn = connect();
+    let id = InvoiceId(NonZeroU32::new(7).unwrap());
+
+    let decoded: InvoiceId = diesel::select((&id).into_sql::<Integer>())
+        .get_result(&mut conn)
+        .unwrap();
+
+    assert_eq!(decoded, id);
+
+    let zero = diesel::select(sql::<Integer>("0")).get_result::<InvoiceId>(&mut conn);
+    assert!(zero.is_err());
+}
+
+#[test]
+fn stores_encoded_text() {
+    let mut conn = connect();
+    let id = TraceId(u128::MAX - 1);
+
+    let (stored, decoded): (String, TraceId) =
+        diesel::select(((&id).into_sql::<Text>(), (&id).into_sql::<Text>()))
+            .get_result(&mut conn)
+            .unwrap();
+
+    assert_eq!(stored, id.to_string());
+    assert_eq!(decoded, id);
+
+    let invalid = diesel::select(sql::<Text>("'not an id'")).get_result::<TraceId>(&mut conn);
+    assert!(invalid.is_err());
+}
+
+#[cfg(feature = "diesel_sqlite")]
+#[test]
+fn table_roundtrip() {
+    let mut conn = connect();
+
+    diesel::insert_into(ids::table)
+        .values((
+            ids::user_id.eq(UserId(1)),
+            ids::order_id.eq(Some(OrderId(u64::MAX))),
+            ids::short_id.eq(Some(ShortId(u16::MAX))),
+            ids::trace_id.eq(Some(TraceId(3))),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+
+    diesel::insert_into(ids::table)
+        .values((
+            ids::user_id.eq(&UserId(2)),
+            ids::order_id.eq(None::<OrderId>),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+
+    let rows: Vec<Row> = ids::table
+        .select((ids::user_id, ids::order_id, ids::short_id, ids::trace_id))
+        .order(ids::user_id)
+        .load(&mut conn)
+        .unwrap();
+
+    assert_eq!(
+        rows,
+        [
+            (
+                UserId(1),
+                Some(OrderId(u64::MAX)),
+                Some(ShortId(u16::MAX)),
+                Some(TraceId(3))
+            ),
+            (UserId(2), None, None, None),
+        ]
+    );
+
+    let found: UserId = ids::table
+        .select(ids::user_id)
+        .filter(ids::order_id.eq(OrderId(u64::MAX)))
+        .first(&mut conn)
+        .unwrap();
+
+    assert_eq!(found, UserId(1));
+}
+
+/// Postgres metadata for built in types, which never needs to be looked up
+#[cfg(feature = "diesel_postgres")]
+struct BuiltinTypes;
+
+#[cfg(feature = "diesel_postgres")]
+impl diesel::pg::PgMetadataLookup for BuiltinTypes {
+    fn lookup_type(&mut self, type_name: &str, _: Option<&str>) -> diesel::pg::PgTypeMetadata {
+        unreachable!("looked up {type_name}, which is not a built in type")
+    }
+}
+
+/// Serialize `value` as a bind parameter of a backend binding bytes, returning the collected binds
+#[cfg(any(feature = "diesel_postgres", feature = "diesel_mysql"))]
+fn binds<DB, ST, T>(value: &T, lookup: &mut DB::MetadataLookup) -> String
+where
+    DB: for<'c> diesel::backend::Backend<BindCollector<'c> = RawBytesBindCollector<DB>>
+        + HasSqlType<ST>,
+    RawBytesBindCollector<DB>: std::fmt::Debug,
+    T: ToSql<ST, DB>,
+{
+    let mut collector = RawBytesBindCollector::<DB>::new();
+    collector
+        .push_bound_value::<ST, T>(value, lookup)
+        .expect("failed to serialize id");
+
+    format!("{collector:?}")
+}
+
+#[cfg(feature = "diesel_postgres")]
+#[test]
+fn postgres_serializes_converted_value() {
+    use diesel::pg::Pg;
+
+    assert_eq!(
+        binds::<Pg, BigInt, _>(&OrderId(u64::MAX), &mut BuiltinTypes),
+        binds::<Pg, BigInt, _>(&-1i64, &mut BuiltinTypes)
+    );
+    assert_eq!(
+        binds::<Pg, SmallInt, _>(&ShortId(40_000), &mut BuiltinTypes),
+        binds::<Pg, SmallInt, _>(&i16::from(ShortId(40_000)), &mut BuiltinTypes)
+    );
+    assert_eq!(
+        binds::<Pg, Integer, _>(&InvoiceId(NonZeroU32::new(7).unwrap()), &mut BuiltinTypes),
+        binds::<Pg, Integer, _>(&7i32, &mut BuiltinTypes)
+    );
+
+    let id = TraceId(u128::MAX - 1);
+    assert_eq!(
+        binds::<Pg, Text, _>(&id, &mut BuiltinTypes),
+        binds::<Pg, Text, _>(&id.to_string(), &mut BuiltinTypes)
+    );
+    assert_ne!(
+        binds::<Pg, BigInt, _>(&OrderId(1), &mut BuiltinTypes),
+        binds::<Pg, BigInt, _>(&OrderId(2), &mut BuiltinTypes)
+    );
+}
+
+#[cfg(feature = "diesel_mysql")]
+#[test]
+fn mysql_serializes_converted_value() {
+    use diesel::mysql::Mysql;
+
+    assert_eq!(
+        binds::<Mysql, BigInt, _>(&OrderId(u64::MAX), &mut ()),
+        binds::<Mysql, BigInt, _>(&-1i64, &mut ())
+    );
+
+    let id = TraceId(3);
+    assert_eq!(
+        binds::<Mysql, Text, _>(&id, &mut ()),
+        binds::<Mysql, Text, _>(&id.to_string(), &mut ())
+    );
+}
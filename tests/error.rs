@@ -4,30 +4,105 @@ macro_rules! generate_error_test_suite {
         mod $lib_type_name {
             use base64id::{
                 Base64Id,
-                Error::{InvalidCharacter, InvalidLength},
+                Error::{InvalidCharacter, InvalidLength, OutOfBoundsCharacter},
             };
             use core::str::FromStr;
 
             #[derive(Base64Id, Debug)]
             struct TestId($int_type);
 
+            const LENGTH_ERROR: base64id::Error = InvalidLength {
+                actual: 1,
+                expected: $bad_char.len(),
+            };
+
             #[test]
             fn bad_length() {
                 let id = TestId::from_str("A").unwrap_err();
-                assert_eq!(id, InvalidLength);
+                assert_eq!(id, LENGTH_ERROR);
+            }
+
+            #[test]
+            fn bad_length_counts_chars() {
+                let id = TestId::from_str("\u{e9}\u{e9}").unwrap_err();
+                assert_eq!(
+                    id,
+                    InvalidLength {
+                        actual: 2,
+                        expected: $bad_char.len(),
+                    }
+                );
             }
 
             #[test]
             fn invalid_character() {
                 let id = TestId::from_str($bad_char).unwrap_err();
-                debug_assert_eq!(id, InvalidCharacter);
+                assert_eq!(
+                    id,
+                    InvalidCharacter {
+                        index: $bad_char.len() - 1,
+                        character: '=',
+                    }
+                );
+            }
+
+            #[test]
+            fn invalid_non_ascii_character() {
+                let input = $bad_char.replace('=', "\u{e9}");
+                let id = TestId::from_str(&input).unwrap_err();
+                assert_eq!(
+                    id,
+                    InvalidCharacter {
+                        index: $bad_char.len() - 1,
+                        character: '\u{e9}',
+                    }
+                );
+            }
+
+            #[test]
+            fn out_of_bounds_character() {
+                let input = $bad_char.replace('=', "B");
+                let id = TestId::from_str(&input).unwrap_err();
+                let OutOfBoundsCharacter {
+                    index,
+                    character,
+                    allowed,
+                } = id
+                else {
+                    panic!("unexpected error {id:?}");
+                };
+
+                assert_eq!(index, $bad_char.len() - 1);
+                assert_eq!(character, 'B');
+                assert!(!allowed.as_str().contains('B'));
+                assert!(allowed.as_str().starts_with('A'));
+            }
+
+            #[test]
+            fn display() {
+                let error = InvalidCharacter {
+                    index: 3,
+                    character: '=',
+                };
+
+                assert_eq!(
+                    error.to_string(),
+                    "invalid character '=' at index 3. character is not within the alphabet"
+                );
+                assert_eq!(
+                    LENGTH_ERROR.to_string(),
+                    format!(
+                        "invalid length. expected exactly {} characters but found 1",
+                        $bad_char.len()
+                    )
+                );
             }
 
             #[test]
             fn core_error_trait_impl() {
                 fn test<E: core::error::Error>(_: E) {}
 
-                let error = InvalidLength;
+                let error = LENGTH_ERROR;
 
                 test(error);
             }
@@ -36,7 +111,7 @@ macro_rules! generate_error_test_suite {
             fn std_error_trait_impl() {
                 fn test<E: std::error::Error>(_: E) {}
 
-                let error = InvalidLength;
+                let error = LENGTH_ERROR;
 
                 test(error);
             }
@@ -0,0 +1,64 @@
+use base64id::Base64Id;
+use core::num::NonZeroU32;
+use core::str::FromStr;
+
+type DbKey = i64;
+type Digest = [u8; 16];
+type OrderKey = NonZeroU32;
+
+#[derive(Base64Id, Debug)]
+struct PrimitiveId(core::primitive::u64);
+
+#[derive(Base64Id, Debug)]
+struct StdPrimitiveId(::std::primitive::i32);
+
+#[derive(Base64Id, Debug)]
+struct NonZeroPrimitiveId(core::num::NonZero<core::primitive::u16>);
+
+#[derive(Base64Id, Debug)]
+#[base64id(inner = i64, Serialize, Deserialize)]
+struct UserId(DbKey);
+
+#[derive(Base64Id, Debug)]
+#[base64id(inner = [u8; 16])]
+struct DigestId(Digest);
+
+#[derive(Base64Id, Debug)]
+#[base64id(inner = NonZeroU32)]
+struct OrderId(OrderKey);
+
+#[test]
+fn primitive_paths() {
+    assert_eq!(PrimitiveId(1).to_string(), "AAAAAAAAAAE");
+    assert_eq!(StdPrimitiveId::from_str("AAAAAQ"), Ok(StdPrimitiveId(1)));
+    assert_eq!(u64::from(PrimitiveId(1)), 1);
+    assert_eq!(i64::from(PrimitiveId(1)), 1);
+
+    let id = NonZeroPrimitiveId(core::num::NonZero::new(7).unwrap());
+    assert_eq!(NonZeroPrimitiveId::from_str(&id.to_string()), Ok(id));
+}
+
+#[test]
+fn aliases() {
+    let key: DbKey = 3161873750843059683;
+
+    assert_eq!(UserId(key).to_string(), "K-E7bI-h7eM");
+    assert_eq!(UserId::from_str("K-E7bI-h7eM"), Ok(UserId::from(key)));
+    assert_eq!(DbKey::from(UserId(key)), key);
+    assert_eq!(u64::from(UserId(key)), key as u64);
+
+    let digest: Digest = [0xAB; 16];
+    assert_eq!(
+        DigestId::from_str(&DigestId(digest).to_string()),
+        Ok(DigestId(digest))
+    );
+
+    assert_eq!(OrderId::try_from(0u32), Err(base64id::Error::ZeroValue));
+    assert_eq!(u32::from(OrderId(OrderKey::new(5).unwrap())), 5);
+}
+
+#[test]
+fn alias_serde() {
+    let json = serde_json::to_string(&UserId(1)).expect("failed to serialize id");
+    assert_eq!(json, "\"AAAAAAAAAAE\"");
+}
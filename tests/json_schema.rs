@@ -0,0 +1,171 @@
+use base64id::Base64Id;
+use core::num::NonZeroU32;
+use schemars::{schema_for, JsonSchema};
+use serde_json::json;
+
+#[derive(Base64Id, Debug)]
+#[base64id(JsonSchema)]
+struct UserId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(JsonSchema)]
+struct ShortId(u16);
+
+#[derive(Base64Id, Debug)]
+#[base64id(JsonSchema)]
+struct InvoiceId(NonZeroU32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(JsonSchema)]
+struct TraceId(u128);
+
+#[derive(Base64Id, Debug)]
+#[base64id(JsonSchema)]
+struct Digest([u8; 4]);
+
+#[derive(Base64Id, Debug)]
+#[base64id(JsonSchema, prefix = "usr.", checksum)]
+struct PrefixedId(i32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(JsonSchema, sortable)]
+struct SortableId(i32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(JsonSchema, Serialize, serde = compact)]
+struct CompactId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(JsonSchema, Deserialize(accept_integer))]
+struct MigratingId(u32);
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+struct User {
+    id: UserId,
+    invoice: Option<InvoiceId>,
+}
+
+#[test]
+fn exact_length_and_pattern() {
+    assert_eq!(
+        schema_for!(UserId).as_value(),
+        &json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "UserId",
+            "type": "string",
+            "minLength": 11,
+            "maxLength": 11,
+            "pattern": r"^[A-Za-z0-9\-_]{10}[AEIMQUYcgkosw048]$",
+            "examples": ["AAAAAAAAAAA"],
+        })
+    );
+
+    let schema = schema_for!(ShortId);
+    assert_eq!(schema.get("minLength"), Some(&json!(3)));
+    assert_eq!(schema.get("maxLength"), Some(&json!(3)));
+    assert_eq!(
+        schema.get("pattern"),
+        Some(&json!(r"^[A-Za-z0-9\-_]{2}[AEIMQUYcgkosw048]$"))
+    );
+
+    let schema = schema_for!(TraceId);
+    assert_eq!(schema.get("minLength"), Some(&json!(22)));
+    assert_eq!(
+        schema.get("pattern"),
+        Some(&json!(r"^[A-Za-z0-9\-_]{21}[AQgw]$"))
+    );
+
+    let schema = schema_for!(Digest);
+    assert_eq!(
+        schema.get("pattern"),
+        Some(&json!(r"^[A-Za-z0-9\-_]{5}[AQgw]$"))
+    );
+    assert_eq!(schema.get("examples"), Some(&json!(["AAAAAA"])));
+}
+
+#[test]
+fn nonzero_example() {
+    let schema = schema_for!(InvoiceId);
+
+    assert_eq!(schema.get("examples"), Some(&json!(["AAAAAQ"])));
+    assert_eq!(
+        schema.get("pattern"),
+        Some(&json!(r"^[A-Za-z0-9\-_]{5}[AQgw]$"))
+    );
+}
+
+#[test]
+fn prefix_and_checksum() {
+    let schema = schema_for!(PrefixedId);
+
+    assert_eq!(schema.get("minLength"), Some(&json!(11)));
+    assert_eq!(schema.get("maxLength"), Some(&json!(11)));
+    assert_eq!(
+        schema.get("pattern"),
+        Some(&json!(r"^usr\.[A-Za-z0-9\-_]{5}[AQgw][A-Za-z0-9\-_]$"))
+    );
+
+    let example = PrefixedId(0).to_string();
+    assert_eq!(schema.get("examples"), Some(&json!([example])));
+}
+
+#[test]
+fn sortable_alphabet() {
+    let schema = schema_for!(SortableId);
+
+    assert_eq!(
+        schema.get("pattern"),
+        Some(&json!(r"^[\-0-9A-Z_a-z]{5}[\-FVk]$"))
+    );
+    assert_eq!(
+        schema.get("examples"),
+        Some(&json!([SortableId(0).to_string()]))
+    );
+}
+
+#[test]
+fn compact_uses_inner_schema() {
+    let schema = schema_for!(CompactId);
+
+    assert_eq!(schema.get("type"), Some(&json!("integer")));
+    assert_eq!(schema.get("pattern"), None);
+}
+
+#[test]
+fn referenced_by_name() {
+    let schema = schema_for!(User);
+    let defs = schema.get("$defs").expect("ids should be definitions");
+
+    assert_eq!(
+        defs["UserId"]["pattern"],
+        json!(r"^[A-Za-z0-9\-_]{10}[AEIMQUYcgkosw048]$")
+    );
+    assert!(defs.get("InvoiceId").is_some());
+    assert_eq!(
+        schema.get("properties").unwrap()["id"],
+        json!({"$ref": "#/$defs/UserId"})
+    );
+}
+
+#[test]
+fn accept_integer_allows_inner_integer() {
+    let schema = schema_for!(MigratingId);
+    let any_of = schema
+        .get("anyOf")
+        .expect("schema should allow either form");
+
+    assert_eq!(
+        any_of[0],
+        json!({
+            "type": "string",
+            "minLength": 6,
+            "maxLength": 6,
+            "pattern": r"^[A-Za-z0-9\-_]{5}[AQgw]$",
+            "examples": ["AAAAAA"],
+        })
+    );
+    assert_eq!(any_of[1]["type"], json!("integer"));
+    assert_eq!(any_of[1]["minimum"], json!(0));
+    assert_eq!(any_of.as_array().map(Vec::len), Some(2));
+}
@@ -0,0 +1,83 @@
+use base64id::{Base64Id, Error, Repairs};
+
+#[derive(Base64Id, Debug)]
+struct MyId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(sortable)]
+struct MySortableId(u32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(alphabet = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz012345678+-_")]
+struct MyPlusId(u64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(prefix = "a+b/")]
+struct MyPrefixedId(u64);
+
+#[test]
+fn canonical_input() {
+    let (id, applied) = MyId::parse_lenient("K-E7bI-h7eM", Repairs::ALL).unwrap();
+
+    assert_eq!(id, MyId(3161873750843059683));
+    assert_eq!(applied, Repairs::NONE);
+}
+
+#[test]
+fn repaired_input() {
+    let (id, applied) = MyId::parse_lenient("\n'K+E7bI+h7eM='\n", Repairs::ALL).unwrap();
+
+    assert_eq!(id, MyId(3161873750843059683));
+    assert_eq!(applied, Repairs::ALL);
+}
+
+#[test]
+fn selected_repairs() {
+    let (id, applied) = MyId::parse_lenient(" K-E7bI-h7eM ", Repairs::TRIM_WHITESPACE).unwrap();
+
+    assert_eq!(id, MyId(3161873750843059683));
+    assert_eq!(applied, Repairs::TRIM_WHITESPACE);
+
+    let err = MyId::parse_lenient(" K+E7bI+h7eM ", Repairs::TRIM_WHITESPACE).unwrap_err();
+
+    assert_eq!(
+        err,
+        Error::InvalidCharacter {
+            index: 2,
+            character: '+'
+        }
+    );
+}
+
+#[test]
+fn sortable() {
+    let encoded = MySortableId(7).to_string();
+    let (id, applied) =
+        MySortableId::parse_lenient(&format!("\"{encoded}\""), Repairs::ALL).unwrap();
+
+    assert_eq!(id, MySortableId(7));
+    assert_eq!(applied, Repairs::STRIP_QUOTES);
+}
+
+#[test]
+fn alphabet_characters_are_not_repaired() {
+    let (id, applied) = MyPlusId::parse_lenient("+AAAAAAAAAA", Repairs::ALL).unwrap();
+
+    assert_eq!(id, MyPlusId(61 << 58));
+    assert_eq!(applied, Repairs::NONE);
+}
+
+#[test]
+fn prefix_is_not_repaired() {
+    let encoded = MyPrefixedId(u64::MAX - 1).to_string();
+    let damaged = format!("a+b/{}", encoded[4..].replace('_', "/"));
+    assert_ne!(damaged, encoded);
+
+    let (id, applied) = MyPrefixedId::parse_lenient(&damaged, Repairs::ALL).unwrap();
+
+    assert_eq!(id, MyPrefixedId(u64::MAX - 1));
+    assert_eq!(applied, Repairs::STANDARD_ALPHABET);
+
+    let err = MyPrefixedId::parse_lenient("a-b_AAAAAAAAAAE", Repairs::ALL).unwrap_err();
+    assert_eq!(err, Error::InvalidPrefix { expected: "a+b/" });
+}
@@ -0,0 +1,85 @@
+use base64id::{Base64Id, Error};
+use core::num::{NonZero, NonZeroI64, NonZeroU64};
+use core::str::FromStr;
+
+#[derive(Base64Id, Debug)]
+#[base64id(Serialize, Deserialize)]
+struct UserId(NonZeroU64);
+
+#[derive(Base64Id, Debug)]
+struct AccountId(NonZeroI64);
+
+#[derive(Base64Id, Debug)]
+struct PathId(core::num::NonZeroU32);
+
+#[derive(Base64Id, Debug)]
+struct StdPathId(std::num::NonZeroI16);
+
+#[derive(Base64Id, Debug)]
+#[base64id(sortable)]
+struct GenericId(NonZero<u128>);
+
+#[test]
+fn option_keeps_niche() {
+    assert_eq!(size_of::<Option<UserId>>(), size_of::<u64>());
+    assert_eq!(size_of::<Option<GenericId>>(), size_of::<u128>());
+}
+
+#[test]
+fn roundtrip() {
+    let id = UserId(NonZeroU64::new(1).unwrap());
+
+    assert_eq!(id.to_string(), "AAAAAAAAAAE");
+    assert_eq!(UserId::from_str("AAAAAAAAAAE"), Ok(id));
+
+    let id = GenericId(NonZero::new(u128::MAX).unwrap());
+    assert_eq!(GenericId::from_str(&id.to_string()), Ok(id));
+}
+
+#[test]
+fn zero_rejected() {
+    assert_eq!(UserId::from_str("AAAAAAAAAAA"), Err(Error::ZeroValue));
+    assert_eq!(PathId::from_str("AAAAAA"), Err(Error::ZeroValue));
+    assert_eq!(StdPathId::try_from(*b"AAA"), Err(Error::ZeroValue));
+
+    let err = serde_json::from_str::<UserId>("\"AAAAAAAAAAA\"").expect_err("deserialized zero");
+    assert!(err
+        .to_string()
+        .starts_with("invalid value: an id of zero, expected a non-zero id"));
+}
+
+#[test]
+fn sortable_zero_rejected() {
+    let zero = base64id::base64::encode_u128_sortable(0);
+    assert_eq!(GenericId::try_from(zero), Err(Error::ZeroValue));
+}
+
+#[test]
+fn integer_conversions() {
+    assert_eq!(u64::from(UserId::try_from(7u64).unwrap()), 7);
+    assert_eq!(UserId::try_from(0u64), Err(Error::ZeroValue));
+    assert_eq!(UserId::try_from(0i64), Err(Error::ZeroValue));
+    assert_eq!(i64::from(UserId::try_from(-1i64).unwrap()), -1);
+    assert_eq!(u64::from(UserId::try_from(-1i64).unwrap()), u64::MAX);
+
+    assert_eq!(
+        AccountId::try_from(u64::MAX),
+        Ok(AccountId(NonZeroI64::new(-1).unwrap()))
+    );
+    assert_eq!(
+        NonZeroI64::from(AccountId::try_from(5i64).unwrap()).get(),
+        5
+    );
+}
+
+#[test]
+fn const_decode() {
+    const ID: UserId = UserId::from_encoded_const("AAAAAAAAAAI");
+    assert_eq!(ID, UserId(NonZeroU64::new(2).unwrap()));
+}
+
+#[test]
+#[should_panic(expected = "decodes to zero")]
+fn const_decode_zero() {
+    let _ = UserId::from_encoded_const("AAAAAAAAAAA");
+}
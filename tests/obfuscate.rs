@@ -0,0 +1,128 @@
+use base64id::{Base64Id, Error, Key};
+use core::num::NonZeroU64;
+use core::str::FromStr;
+use std::sync::LazyLock;
+
+const KEY: Key = Key::new(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210);
+
+static RUNTIME_KEY: LazyLock<Key> = LazyLock::new(|| {
+    let material = std::env::var("BASE64ID_TEST_KEY").unwrap_or_else(|_| "42".into());
+    Key::new(material.parse().expect("invalid test key"))
+});
+
+fn key_from_fn() -> &'static Key {
+    &KEY
+}
+
+#[derive(Base64Id, Debug)]
+#[base64id(obfuscate = KEY, Serialize, Deserialize)]
+struct UserId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(obfuscate = RUNTIME_KEY)]
+struct OrderId(u32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(obfuscate = key_from_fn())]
+struct InvoiceId(NonZeroU64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(obfuscate = Key::new(7))]
+struct TinyId(u16);
+
+#[derive(Base64Id, Debug)]
+#[base64id(obfuscate = KEY)]
+struct WideId(u128);
+
+#[derive(Base64Id, Debug)]
+#[base64id(obfuscate = KEY, Serialize, Deserialize)]
+struct ReceiptId(NonZeroU64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(obfuscate = KEY, Deserialize(accept_integer))]
+struct MigratingId(i64);
+
+#[test]
+fn sequential_ids_are_not_sequential() {
+    let first = UserId(1).to_string();
+    let second = UserId(2).to_string();
+
+    assert_ne!(first, "AAAAAAAAAAE");
+    assert_ne!(first[..8], second[..8]);
+}
+
+#[test]
+fn encodes_obfuscated_value() {
+    let expected = base64id::base64::encode_i64_bytes(KEY.obfuscate_i64(1));
+    assert_eq!(UserId(1).encode().as_bytes(), &expected);
+}
+
+#[test]
+fn roundtrip() {
+    for v in [i64::MIN, -1, 0, 1, 2, i64::MAX] {
+        let id = UserId(v);
+        assert_eq!(UserId::from_str(&id.encode()), Ok(id));
+    }
+
+    for v in [0, 1, u32::MAX] {
+        let id = OrderId(v);
+        assert_eq!(OrderId::from_str(&id.to_string()), Ok(id));
+    }
+
+    for v in [0, 1, u128::MAX] {
+        let id = WideId(v);
+        assert_eq!(WideId::from_str(&id.to_string()), Ok(id));
+    }
+}
+
+#[test]
+fn bijective_16_bit() {
+    let mut seen = std::collections::HashSet::new();
+
+    for v in 0..=u16::MAX {
+        let encoded = TinyId(v).to_string();
+        assert_eq!(TinyId::from_str(&encoded), Ok(TinyId(v)));
+        assert!(seen.insert(encoded));
+    }
+}
+
+#[test]
+fn nonzero() {
+    let id = InvoiceId(NonZeroU64::new(1).unwrap());
+    assert_eq!(InvoiceId::from_str(&id.to_string()), Ok(id));
+
+    let zero = base64id::base64::encode_u64_bytes(KEY.obfuscate_u64(0));
+    assert_eq!(InvoiceId::try_from(zero), Err(Error::ZeroValue));
+}
+
+#[test]
+fn serde() {
+    let json = serde_json::to_string(&UserId(1)).expect("failed to serialize id");
+    assert_eq!(json, format!("\"{}\"", UserId(1)));
+
+    let id: UserId = serde_json::from_str(&json).expect("failed to deserialize id");
+    assert_eq!(id, UserId(1));
+}
+
+#[test]
+fn binary_serde_obfuscates_inner_value() {
+    let encoded = bincode::serialize(&UserId(1)).expect("failed to serialize id");
+    assert_ne!(encoded, 1i64.to_le_bytes());
+    assert_eq!(encoded, KEY.obfuscate_i64(1).to_le_bytes());
+    assert_eq!(bincode::deserialize::<UserId>(&encoded).unwrap(), UserId(1));
+
+    let id = ReceiptId(NonZeroU64::new(1).unwrap());
+    let encoded = bincode::serialize(&id).expect("failed to serialize id");
+    assert_eq!(encoded, KEY.obfuscate_u64(1).to_le_bytes());
+    assert_eq!(bincode::deserialize::<ReceiptId>(&encoded).unwrap(), id);
+
+    let zero = bincode::serialize(&KEY.obfuscate_u64(0)).unwrap();
+    assert!(bincode::deserialize::<ReceiptId>(&zero).is_err());
+}
+
+#[test]
+fn accept_integer_deobfuscates() {
+    let json = KEY.obfuscate_i64(1).to_string();
+    let id: MigratingId = serde_json::from_str(&json).expect("failed to deserialize id");
+    assert_eq!(id, MigratingId(1));
+}
@@ -0,0 +1,115 @@
+use base64id::{Base64Id, Error};
+use bytes::BytesMut;
+use core::num::NonZeroU32;
+use postgres_types::{FromSql, IsNull, ToSql, Type};
+
+#[derive(Base64Id, Debug)]
+#[base64id(postgres)]
+struct UserId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(postgres)]
+struct OrderId(u64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(postgres)]
+struct ShortId(u16);
+
+#[derive(Base64Id, Debug)]
+#[base64id(postgres)]
+struct InvoiceId(NonZeroU32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(postgres)]
+struct TraceId(u128);
+
+#[derive(Base64Id, Debug)]
+#[base64id(postgres)]
+struct Digest([u8; 32]);
+
+/// Encode `value` in the binary wire format of `ty`
+fn encode(value: &dyn ToSql, ty: &Type) -> Vec<u8> {
+    let mut buf = BytesMut::new();
+    let is_null = value
+        .to_sql_checked(ty, &mut buf)
+        .expect("failed to encode id");
+
+    assert!(matches!(is_null, IsNull::No));
+    buf.to_vec()
+}
+
+#[test]
+fn stores_signed_integer() {
+    let raw = encode(&UserId(-42), &Type::INT8);
+
+    assert_eq!(raw, (-42i64).to_be_bytes());
+    assert_eq!(UserId::from_sql(&Type::INT8, &raw).unwrap(), UserId(-42));
+}
+
+#[test]
+fn stores_unsigned_as_signed() {
+    let raw = encode(&OrderId(u64::MAX), &Type::INT8);
+    assert_eq!(raw, (-1i64).to_be_bytes());
+    assert_eq!(
+        OrderId::from_sql(&Type::INT8, &raw).unwrap(),
+        OrderId(u64::MAX)
+    );
+
+    let raw = encode(&ShortId(40_000), &Type::INT2);
+    assert_eq!(raw, 40_000u16.to_be_bytes());
+    assert_eq!(
+        ShortId::from_sql(&Type::INT2, &raw).unwrap(),
+        ShortId(40_000)
+    );
+}
+
+#[test]
+fn accepts_type_of_same_width() {
+    assert!(<UserId as ToSql>::accepts(&Type::INT8));
+    assert!(!<UserId as ToSql>::accepts(&Type::INT4));
+    assert!(<InvoiceId as ToSql>::accepts(&Type::INT4));
+    assert!(<ShortId as FromSql>::accepts(&Type::INT2));
+    assert!(!<ShortId as FromSql>::accepts(&Type::TEXT));
+
+    assert!(UserId(1)
+        .to_sql_checked(&Type::INT4, &mut BytesMut::new())
+        .is_err());
+}
+
+#[test]
+fn nonzero() {
+    let id = InvoiceId(NonZeroU32::new(7).unwrap());
+
+    let raw = encode(&id, &Type::INT4);
+    assert_eq!(InvoiceId::from_sql(&Type::INT4, &raw).unwrap(), id);
+
+    let error = InvoiceId::from_sql(&Type::INT4, &[0; 4]).unwrap_err();
+    assert_eq!(error.downcast_ref::<Error>(), Some(&Error::ZeroValue));
+}
+
+#[test]
+fn stores_128_bit_as_uuid_or_bytea() {
+    let id = TraceId(0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10);
+    let expected: Vec<u8> = (1..=16).collect();
+
+    for ty in [Type::UUID, Type::BYTEA] {
+        let raw = encode(&id, &ty);
+
+        assert_eq!(raw, expected);
+        assert_eq!(TraceId::from_sql(&ty, &raw).unwrap(), id);
+    }
+
+    assert!(!<TraceId as ToSql>::accepts(&Type::INT8));
+    assert!(TraceId::from_sql(&Type::BYTEA, &[1, 2]).is_err());
+}
+
+#[test]
+fn stores_bytes_as_bytea() {
+    let id = Digest([7; 32]);
+
+    let raw = encode(&id, &Type::BYTEA);
+
+    assert_eq!(raw, [7; 32]);
+    assert_eq!(Digest::from_sql(&Type::BYTEA, &raw).unwrap(), id);
+    assert!(!<Digest as ToSql>::accepts(&Type::UUID));
+}
@@ -0,0 +1,112 @@
+use base64id::{Base64Id, Error, Repairs};
+use core::str::FromStr;
+
+#[derive(Base64Id, Debug)]
+#[base64id(prefix = "usr_", Serialize, Deserialize)]
+struct UserId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(prefix = "ord_")]
+struct OrderId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(prefix = "inv-", checksum, sortable)]
+struct InvoiceId(u32);
+
+#[test]
+fn display_and_encode() {
+    assert_eq!(UserId(1).to_string(), "usr_AAAAAAAAAAE");
+    assert_eq!(UserId(1).encode(), "usr_AAAAAAAAAAE");
+    assert_eq!(OrderId(1).encode().len(), 15);
+}
+
+#[test]
+fn from_str() {
+    assert_eq!(UserId::from_str("usr_AAAAAAAAAAE"), Ok(UserId(1)));
+    assert_eq!(UserId::try_from(*b"usr_AAAAAAAAAAE"), Ok(UserId(1)));
+
+    const ID: UserId = UserId::from_encoded_const("usr_AAAAAAAAAAE");
+    assert_eq!(ID, UserId(1));
+}
+
+#[test]
+#[should_panic(expected = "does not start with the expected prefix")]
+fn const_invalid_prefix() {
+    let _ = UserId::from_encoded_const("ord_AAAAAAAAAAE");
+}
+
+#[test]
+fn invalid_prefix() {
+    let prefix_err = Err(Error::InvalidPrefix { expected: "usr_" });
+
+    assert_eq!(UserId::from_str("AAAAAAAAAAE"), prefix_err);
+    assert_eq!(UserId::from_str(&OrderId(1).to_string()), prefix_err);
+    assert_eq!(UserId::from_str(""), prefix_err);
+}
+
+#[test]
+fn errors_refer_to_whole_input() {
+    assert_eq!(
+        UserId::from_str("usr_AAAAAAAAAA"),
+        Err(Error::InvalidLength {
+            actual: 14,
+            expected: 15
+        })
+    );
+    assert_eq!(
+        UserId::from_str("usr_AAAAAAAAAAé"),
+        Err(Error::InvalidCharacter {
+            index: 14,
+            character: 'é'
+        })
+    );
+    assert!(matches!(
+        UserId::from_str("usr_AAAAAAAAAAB"),
+        Err(Error::OutOfBoundsCharacter { index: 14, .. })
+    ));
+}
+
+#[test]
+fn with_checksum() {
+    let encoded = InvoiceId(7).to_string();
+
+    assert!(encoded.starts_with("inv-"));
+    assert_eq!(encoded.len(), 11);
+    assert_eq!(InvoiceId::from_str(&encoded), Ok(InvoiceId(7)));
+
+    let mut typo = encoded.into_bytes();
+    typo[6] = b'z';
+    assert_eq!(
+        InvoiceId::try_from(<[u8; 11]>::try_from(typo).unwrap()),
+        Err(Error::ChecksumMismatch)
+    );
+}
+
+#[test]
+fn lenient() {
+    let (id, applied) = UserId::parse_lenient(" \"usr_AAAAAAAAAAE\" ", Repairs::ALL).unwrap();
+
+    assert_eq!(id, UserId(1));
+    assert_eq!(applied, Repairs::TRIM_WHITESPACE | Repairs::STRIP_QUOTES);
+}
+
+#[test]
+fn serde() {
+    let json = serde_json::to_string(&UserId(1)).expect("failed to serialize id");
+    assert_eq!(json, "\"usr_AAAAAAAAAAE\"");
+
+    let id: UserId = serde_json::from_str(&json).expect("failed to deserialize id");
+    assert_eq!(id, UserId(1));
+
+    let err = serde_json::from_str::<UserId>("\"ord_AAAAAAAAAAE\"").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: string \"ord_AAAAAAAAAAE\", expected an id starting with `usr_` at line 1 column 17"
+    );
+
+    let err = serde_json::from_str::<UserId>("\"usr_AAAA\"").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 8, expected exactly 15 characters, starting with `usr_` followed by 11 base64url characters at line 1 column 10"
+    );
+}
@@ -0,0 +1,174 @@
+use base64id::{Base64Id, Error};
+use core::num::NonZeroU32;
+use rusqlite::types::FromSqlError;
+use rusqlite::Connection;
+
+#[derive(Base64Id, Debug)]
+#[base64id(rusqlite)]
+struct UserId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(rusqlite)]
+struct OrderId(u64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(rusqlite)]
+struct ShortId(u16);
+
+#[derive(Base64Id, Debug)]
+#[base64id(rusqlite)]
+struct InvoiceId(NonZeroU32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(rusqlite)]
+struct TraceId(u128);
+
+#[derive(Base64Id, Debug)]
+#[base64id(rusqlite)]
+struct Digest([u8; 32]);
+
+#[derive(Base64Id, Debug)]
+#[base64id(rusqlite = Text)]
+struct SessionId(i64);
+
+fn connect() -> Connection {
+    Connection::open_in_memory().expect("failed to open database")
+}
+
+/// Select the given parameter, returning it as a raw value and as `T`
+fn roundtrip<S, T>(conn: &Connection, value: impl rusqlite::ToSql) -> (S, T)
+where
+    S: rusqlite::types::FromSql,
+    T: rusqlite::types::FromSql,
+{
+    conn.query_row("SELECT ?1, ?1", [value], |row| {
+        Ok((row.get(0)?, row.get(1)?))
+    })
+    .unwrap()
+}
+
+/// Decode the single value returned by `sql`, returning the base64id error wrapped by `FromSqlError::Other`
+fn decode_error<T: rusqlite::types::FromSql>(conn: &Connection, sql: &str) -> Option<Error> {
+    let error = conn
+        .query_row(sql, [], |row| Ok(T::column_result(row.get_ref(0)?).err()))
+        .unwrap();
+
+    match error {
+        Some(FromSqlError::Other(e)) => e.downcast_ref::<Error>().copied(),
+        _ => None,
+    }
+}
+
+#[test]
+fn stores_signed_integer() {
+    let conn = connect();
+
+    let (stored, id): (i64, UserId) = roundtrip(&conn, UserId(-42));
+
+    assert_eq!(stored, -42);
+    assert_eq!(id, UserId(-42));
+}
+
+#[test]
+fn stores_unsigned_as_signed() {
+    let conn = connect();
+
+    let (stored, id): (i64, OrderId) = roundtrip(&conn, OrderId(u64::MAX));
+    assert_eq!(stored, -1);
+    assert_eq!(id, OrderId(u64::MAX));
+
+    let (stored, id): (i16, ShortId) = roundtrip(&conn, ShortId(40_000));
+    assert_eq!(stored, i16::from(ShortId(40_000)));
+    assert_eq!(id, ShortId(40_000));
+}
+
+#[test]
+fn integer_out_of_range() {
+    let conn = connect();
+
+    let result: rusqlite::Result<ShortId> = conn.query_row("SELECT 70000", [], |row| row.get(0));
+    assert!(matches!(
+        result,
+        Err(rusqlite::Error::IntegralValueOutOfRange(0, 70000))
+    ));
+}
+
+#[test]
+fn nonzero() {
+    let conn = connect();
+    let id = InvoiceId(NonZeroU32::new(7).unwrap());
+
+    let (_, decoded): (i64, InvoiceId) = roundtrip(&conn, &id);
+    assert_eq!(decoded, id);
+
+    let zero = decode_error::<InvoiceId>(&conn, "SELECT 0");
+    assert_eq!(zero, Some(Error::ZeroValue));
+}
+
+#[test]
+fn stores_128_bit_as_blob() {
+    let conn = connect();
+    let id = TraceId(0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10);
+
+    let (stored, decoded): (Vec<u8>, TraceId) = roundtrip(&conn, &id);
+
+    assert_eq!(stored, (1..=16).collect::<Vec<u8>>());
+    assert_eq!(decoded, id);
+
+    let result: rusqlite::Result<TraceId> = conn.query_row("SELECT x'0102'", [], |row| row.get(0));
+    assert!(matches!(
+        result,
+        Err(rusqlite::Error::FromSqlConversionFailure(..))
+    ));
+}
+
+#[test]
+fn stores_bytes_as_blob() {
+    let conn = connect();
+    let id = Digest([7; 32]);
+
+    let (stored, decoded): (Vec<u8>, Digest) = roundtrip(&conn, &id);
+
+    assert_eq!(stored, [7; 32]);
+    assert_eq!(decoded, id);
+}
+
+#[test]
+fn stores_encoded_text() {
+    let conn = connect();
+
+    let (stored, id): (String, SessionId) = roundtrip(&conn, SessionId(1));
+    assert_eq!(stored, "AAAAAAAAAAE");
+    assert_eq!(id, SessionId(1));
+
+    let error = decode_error::<SessionId>(&conn, "SELECT 'AAAAAAAAAA='");
+    assert_eq!(
+        error,
+        Some(Error::InvalidCharacter {
+            index: 10,
+            character: '='
+        })
+    );
+}
+
+#[test]
+fn table_roundtrip() {
+    let conn = connect();
+    conn.execute_batch("CREATE TABLE users (id INTEGER PRIMARY KEY, session TEXT NOT NULL)")
+        .unwrap();
+
+    conn.execute(
+        "INSERT INTO users (id, session) VALUES (?1, ?2)",
+        (UserId(3), SessionId(4)),
+    )
+    .unwrap();
+
+    let (id, session): (UserId, SessionId) = conn
+        .query_row("SELECT id, session FROM users", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+
+    assert_eq!(id, UserId(3));
+    assert_eq!(session, SessionId(4));
+}
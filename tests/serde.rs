@@ -0,0 +1,257 @@
+use base64id::Base64Id;
+use core::num::NonZeroU32;
+use serde::de::value::{BorrowedBytesDeserializer, BytesDeserializer, Error as ValueError};
+use serde::Deserialize;
+
+#[derive(Base64Id, Debug)]
+#[base64id(Serialize, Deserialize)]
+struct UserId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(Serialize, Deserialize)]
+struct ShortId(u16);
+
+#[derive(Base64Id, Debug)]
+#[base64id(Serialize, Deserialize)]
+struct InvoiceId(NonZeroU32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(Serialize, Deserialize)]
+struct TraceId(u128);
+
+#[derive(Base64Id, Debug)]
+#[base64id(Serialize, Deserialize)]
+struct Digest([u8; 4]);
+
+#[derive(Base64Id, Debug)]
+#[base64id(Serialize, Deserialize, serde = string)]
+struct LegacyId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(Serialize, Deserialize, serde = compact)]
+struct CompactId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(Serialize, Deserialize, serde = compact)]
+struct CompactDigest([u8; 4]);
+
+#[test]
+fn human_readable_formats_use_string() {
+    assert_eq!(
+        serde_json::to_string(&UserId(1)).unwrap(),
+        "\"AAAAAAAAAAE\""
+    );
+    assert_eq!(
+        serde_json::from_str::<UserId>("\"AAAAAAAAAAE\"").unwrap(),
+        UserId(1)
+    );
+}
+
+#[test]
+fn binary_formats_use_inner_value() {
+    let encoded = bincode::serialize(&UserId(-2)).unwrap();
+    assert_eq!(encoded, (-2i64).to_le_bytes());
+    assert_eq!(
+        bincode::deserialize::<UserId>(&encoded).unwrap(),
+        UserId(-2)
+    );
+
+    let encoded = bincode::serialize(&ShortId(u16::MAX)).unwrap();
+    assert_eq!(encoded, u16::MAX.to_le_bytes());
+    assert_eq!(
+        bincode::deserialize::<ShortId>(&encoded).unwrap(),
+        ShortId(u16::MAX)
+    );
+
+    let encoded = bincode::serialize(&TraceId(u128::MAX)).unwrap();
+    assert_eq!(encoded, u128::MAX.to_le_bytes());
+    assert_eq!(
+        bincode::deserialize::<TraceId>(&encoded).unwrap(),
+        TraceId(u128::MAX)
+    );
+
+    let encoded = bincode::serialize(&Digest([1, 2, 3, 4])).unwrap();
+    assert_eq!(encoded, [4, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4]);
+    assert_eq!(
+        bincode::deserialize::<Digest>(&encoded).unwrap(),
+        Digest([1, 2, 3, 4])
+    );
+}
+
+#[test]
+fn binary_formats_check_range() {
+    let id = InvoiceId(NonZeroU32::new(7).unwrap());
+    let encoded = rmp_serde::to_vec(&id).unwrap();
+    assert_eq!(rmp_serde::from_slice::<InvoiceId>(&encoded).unwrap(), id);
+
+    let zero = rmp_serde::to_vec(&0u32).unwrap();
+    let err = rmp_serde::from_slice::<InvoiceId>(&zero).expect_err("deserialized a zero id");
+    assert!(err.to_string().contains("a non-zero id"));
+
+    let large = rmp_serde::to_vec(&70_000u32).unwrap();
+    assert!(rmp_serde::from_slice::<ShortId>(&large).is_err());
+
+    let negative = rmp_serde::to_vec(&-1i64).unwrap();
+    assert!(rmp_serde::from_slice::<ShortId>(&negative).is_err());
+}
+
+#[test]
+fn message_pack_uses_inner_value() {
+    let compact = rmp_serde::to_vec(&UserId(1)).unwrap();
+    assert_eq!(compact, rmp_serde::to_vec(&1i64).unwrap());
+    assert_eq!(
+        rmp_serde::from_slice::<UserId>(&compact).unwrap(),
+        UserId(1)
+    );
+}
+
+#[test]
+fn forced_string() {
+    let encoded = bincode::serialize(&LegacyId(1)).unwrap();
+    assert_eq!(encoded, bincode::serialize("AAAAAAAAAAE").unwrap());
+    assert_eq!(
+        bincode::deserialize::<LegacyId>(&encoded).unwrap(),
+        LegacyId(1)
+    );
+}
+
+#[test]
+fn forced_compact() {
+    assert_eq!(serde_json::to_string(&CompactId(-5)).unwrap(), "-5");
+    assert_eq!(
+        serde_json::from_str::<CompactId>("-5").unwrap(),
+        CompactId(-5)
+    );
+
+    let json = serde_json::to_string(&CompactDigest([1, 2, 3, 4])).unwrap();
+    assert_eq!(json, "[1,2,3,4]");
+    assert_eq!(
+        serde_json::from_str::<CompactDigest>(&json).unwrap(),
+        CompactDigest([1, 2, 3, 4])
+    );
+    assert!(serde_json::from_str::<CompactDigest>("[1,2,3]").is_err());
+    assert!(serde_json::from_str::<CompactDigest>("[1,2,3,4,5]").is_err());
+}
+
+#[derive(Base64Id, Debug)]
+#[base64id(Serialize, Deserialize(accept_integer))]
+struct MigratingId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(Deserialize(accept_integer))]
+struct MigratingShortId(u16);
+
+#[derive(Base64Id, Debug)]
+#[base64id(Deserialize(accept_integer))]
+struct MigratingTraceId(u128);
+
+#[test]
+fn accept_integer() {
+    let id: MigratingId = serde_json::from_str("4337351837722417824").unwrap();
+    assert_eq!(id, MigratingId(4337351837722417824));
+    assert_eq!(id.to_string(), "PDFehCFVGqA");
+
+    let id: MigratingId = serde_json::from_str("\"PDFehCFVGqA\"").unwrap();
+    assert_eq!(id, MigratingId(4337351837722417824));
+
+    let id: MigratingId = serde_json::from_str("-1").unwrap();
+    assert_eq!(id, MigratingId(-1));
+
+    let id: MigratingTraceId = serde_json::from_str("18446744073709551615").unwrap();
+    assert_eq!(id, MigratingTraceId(u64::MAX as u128));
+
+    assert_eq!(
+        serde_json::to_string(&MigratingId(1)).unwrap(),
+        "\"AAAAAAAAAAE\""
+    );
+}
+
+#[test]
+fn accept_integer_checks_range() {
+    let id: MigratingShortId = serde_json::from_str("65535").unwrap();
+    assert_eq!(id, MigratingShortId(u16::MAX));
+
+    let err = serde_json::from_str::<MigratingShortId>("65536")
+        .expect_err("deserialized an out of range id");
+    assert!(err.to_string().starts_with(
+        "invalid value: integer `65536`, expected a MigratingShortId encoded as exactly 3 base64url characters or an integer"
+    ));
+
+    let err =
+        serde_json::from_str::<MigratingShortId>("-1").expect_err("deserialized a negative id");
+    assert!(err.to_string().starts_with("invalid value: integer `-1`"));
+
+    assert!(serde_json::from_str::<MigratingShortId>("1.5").is_err());
+}
+
+#[test]
+fn integers_rejected_by_default() {
+    let err = serde_json::from_str::<UserId>("1").expect_err("deserialized an integer");
+    assert!(err.to_string().starts_with("invalid type: integer `1`"));
+}
+
+#[derive(Base64Id, Debug)]
+#[base64id(prefix = "usr_", Deserialize)]
+struct PrefixedId(i64);
+
+#[test]
+fn expecting_names_type_and_length() {
+    let err = serde_json::from_str::<UserId>("true").expect_err("deserialized a bool");
+    assert!(err.to_string().starts_with(
+        "invalid type: boolean `true`, expected a UserId encoded as exactly 11 base64url characters"
+    ));
+
+    let err = serde_json::from_str::<PrefixedId>("true").expect_err("deserialized a bool");
+    assert!(err.to_string().starts_with(
+        "invalid type: boolean `true`, expected a PrefixedId encoded as exactly 15 characters, starting with `usr_` followed by 11 base64url characters"
+    ));
+}
+
+#[test]
+fn deserialize_from_bytes() {
+    let id = UserId::deserialize(BytesDeserializer::<ValueError>::new(b"AAAAAAAAAAE")).unwrap();
+    assert_eq!(id, UserId(1));
+
+    let id =
+        UserId::deserialize(BorrowedBytesDeserializer::<ValueError>::new(b"AAAAAAAAAAE")).unwrap();
+    assert_eq!(id, UserId(1));
+
+    let err = UserId::deserialize(BytesDeserializer::<ValueError>::new(b"AAAAAAAAAA="))
+        .expect_err("deserialized invalid bytes");
+    assert_eq!(
+        err.to_string(),
+        "invalid value: character `=` at index 10, expected exactly 11 base64url characters"
+    );
+
+    let err = UserId::deserialize(BytesDeserializer::<ValueError>::new(b"AAAA"))
+        .expect_err("deserialized too few bytes");
+    assert_eq!(
+        err.to_string(),
+        "invalid length 4, expected exactly 11 base64url characters"
+    );
+}
+
+#[test]
+fn deserialize_byte_array_from_raw_or_encoded_bytes() {
+    let raw = rmp_serde::to_vec(&Bin(&[1, 2, 3, 4])).unwrap();
+    assert_eq!(
+        rmp_serde::from_slice::<Digest>(&raw).unwrap(),
+        Digest([1, 2, 3, 4])
+    );
+
+    let encoded = Digest([1, 2, 3, 4]).to_string();
+    let ascii = rmp_serde::to_vec(&Bin(encoded.as_bytes())).unwrap();
+    assert_eq!(
+        rmp_serde::from_slice::<Digest>(&ascii).unwrap(),
+        Digest([1, 2, 3, 4])
+    );
+}
+
+/// A slice serialized as bytes, such as MessagePack bin, rather than a sequence
+struct Bin<'a>(&'a [u8]);
+
+impl serde::Serialize for Bin<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
@@ -0,0 +1,62 @@
+use base64id::{Base64Id, Snowflake, SnowflakeParts};
+use core::cell::Cell;
+use core::str::FromStr;
+
+const EPOCH: u64 = 1_577_836_800_000;
+const SNOWFLAKE: Snowflake = Snowflake::new(EPOCH, 10, 12);
+
+#[derive(Base64Id, Debug)]
+#[base64id(sortable)]
+struct EventId(i64);
+
+#[derive(Base64Id, Debug)]
+struct OrderId(u64);
+
+#[test]
+fn generates_derived_ids() {
+    let now = Cell::new(EPOCH + 1_000);
+    let generator = SNOWFLAKE.generator(42, || now.get());
+
+    let first: EventId = generator.generate();
+    now.set(EPOCH + 2_000);
+    let second: EventId = generator.generate();
+
+    assert!(first.to_string() < second.to_string());
+    assert!(i64::from(first) > 0);
+
+    let order: OrderId = generator.generate();
+    assert_eq!(SNOWFLAKE.decompose(order).sequence, 1);
+}
+
+#[test]
+fn decompose_derived_ids() {
+    let generator = SNOWFLAKE.generator(42, || EPOCH + 1_000);
+    let encoded = generator.generate::<EventId>().to_string();
+
+    let id = EventId::from_str(&encoded).unwrap();
+
+    assert_eq!(
+        SNOWFLAKE.decompose(id),
+        SnowflakeParts {
+            timestamp: EPOCH + 1_000,
+            worker: 42,
+            sequence: 0
+        }
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn system_clock() {
+    use base64id::SystemClock;
+
+    let generator = SNOWFLAKE.generator(1, SystemClock);
+    let before = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+
+    let id: EventId = generator.generate();
+
+    assert!(SNOWFLAKE.decompose(id).timestamp >= before);
+}
@@ -0,0 +1,63 @@
+macro_rules! generate_sortable_test_suite {
+    ($test_suite:ident, $int_type:ident) => {
+        #[cfg(test)]
+        mod $test_suite {
+            use base64id::Base64Id;
+            use core::str::FromStr;
+
+            #[derive(Base64Id, Debug, Clone, Copy)]
+            #[base64id(sortable, Serialize, Deserialize)]
+            struct SortableId($int_type);
+
+            fn values() -> Vec<$int_type> {
+                let mut values = vec![$int_type::MIN, $int_type::MAX, 0, 1];
+                values.extend((0..100).map(|_| rand::random::<$int_type>()));
+                values
+            }
+
+            #[test]
+            fn string_order_matches_integer_order() {
+                let mut ids: Vec<SortableId> = values().into_iter().map(SortableId).collect();
+                let mut strings: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+
+                ids.sort_by_key(|id| id.0);
+                strings.sort();
+
+                let sorted: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+                assert_eq!(sorted, strings);
+            }
+
+            #[test]
+            fn roundtrip() {
+                for v in values() {
+                    let id = SortableId(v);
+                    assert_eq!(SortableId::from_str(&id.to_string()), Ok(id));
+                }
+            }
+
+            #[test]
+            fn serde_roundtrip() {
+                for v in values() {
+                    let id = SortableId(v);
+                    let json = serde_json::to_string(&id).expect("failed to serialize id");
+                    let output: SortableId =
+                        serde_json::from_str(&json).expect("failed to deserialize id");
+
+                    assert_eq!(output, id);
+                }
+            }
+        }
+    };
+}
+
+generate_sortable_test_suite!(sortable_128_i, i128);
+generate_sortable_test_suite!(sortable_128_u, u128);
+
+generate_sortable_test_suite!(sortable_64_i, i64);
+generate_sortable_test_suite!(sortable_64_u, u64);
+
+generate_sortable_test_suite!(sortable_32_i, i32);
+generate_sortable_test_suite!(sortable_32_u, u32);
+
+generate_sortable_test_suite!(sortable_16_i, i16);
+generate_sortable_test_suite!(sortable_16_u, u16);
@@ -0,0 +1,154 @@
+use base64id::Base64Id;
+use core::num::NonZeroU32;
+use sqlx::{Connection, SqliteConnection};
+
+#[derive(Base64Id, Debug)]
+#[base64id(sqlx)]
+struct UserId(i64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(sqlx)]
+struct OrderId(u64);
+
+#[derive(Base64Id, Debug)]
+#[base64id(sqlx)]
+struct ShortId(u16);
+
+#[derive(Base64Id, Debug)]
+#[base64id(sqlx)]
+struct InvoiceId(NonZeroU32);
+
+#[derive(Base64Id, Debug)]
+#[base64id(sqlx)]
+struct TraceId(u128);
+
+#[derive(Base64Id, Debug)]
+#[base64id(sqlx)]
+struct Digest([u8; 32]);
+
+async fn connect() -> SqliteConnection {
+    SqliteConnection::connect("sqlite::memory:")
+        .await
+        .expect("failed to open database")
+}
+
+#[tokio::test]
+async fn stores_signed_integer() {
+    let mut conn = connect().await;
+
+    let (stored, id): (i64, UserId) = sqlx::query_as("SELECT ?1, ?1")
+        .bind(UserId(-42))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(stored, -42);
+    assert_eq!(id, UserId(-42));
+}
+
+#[tokio::test]
+async fn stores_unsigned_as_signed() {
+    let mut conn = connect().await;
+
+    let (stored, id): (i64, OrderId) = sqlx::query_as("SELECT ?1, ?1")
+        .bind(OrderId(u64::MAX))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(stored, -1);
+    assert_eq!(id, OrderId(u64::MAX));
+
+    let (stored, id): (i16, ShortId) = sqlx::query_as("SELECT ?1, ?1")
+        .bind(ShortId(40_000))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(stored, i16::from(ShortId(40_000)));
+    assert_eq!(id, ShortId(40_000));
+}
+
+#[tokio::test]
+async fn nonzero() {
+    let mut conn = connect().await;
+    let id = InvoiceId(NonZeroU32::new(7).unwrap());
+
+    let (decoded,): (InvoiceId,) = sqlx::query_as("SELECT ?")
+        .bind(id)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(decoded, InvoiceId(NonZeroU32::new(7).unwrap()));
+
+    let zero = sqlx::query_as::<_, (InvoiceId,)>("SELECT 0")
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(zero.is_err());
+}
+
+#[tokio::test]
+async fn stores_bytes() {
+    let mut conn = connect().await;
+
+    let (stored, id): (Vec<u8>, TraceId) = sqlx::query_as("SELECT ?1, ?1")
+        .bind(TraceId(1))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(stored, 1u128.to_be_bytes());
+    assert_eq!(id, TraceId(1));
+
+    let (digest,): (Digest,) = sqlx::query_as("SELECT ?")
+        .bind(Digest([0xAB; 32]))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(digest, Digest([0xAB; 32]));
+}
+
+#[tokio::test]
+async fn table_roundtrip() {
+    let mut conn = connect().await;
+
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    sqlx::query("INSERT INTO users (id, name) VALUES (?, ?)")
+        .bind(UserId(3161873750843059683))
+        .bind("alice")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    let (id, name): (UserId, String) = sqlx::query_as("SELECT id, name FROM users")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(id.to_string(), "K-E7bI-h7eM");
+    assert_eq!(name, "alice");
+}
+
+/// Ids are stored natively by every database, so only need to compile for Postgres and MySQL
+#[test]
+fn implemented_for_all_databases() {
+    fn assert_impls<DB: sqlx::Database, T>()
+    where
+        T: sqlx::Type<DB> + for<'q> sqlx::Encode<'q, DB> + for<'r> sqlx::Decode<'r, DB>,
+    {
+    }
+
+    assert_impls::<sqlx::Postgres, OrderId>();
+    assert_impls::<sqlx::Postgres, ShortId>();
+    assert_impls::<sqlx::Postgres, InvoiceId>();
+    assert_impls::<sqlx::Postgres, TraceId>();
+    assert_impls::<sqlx::MySql, UserId>();
+    assert_impls::<sqlx::MySql, Digest>();
+}
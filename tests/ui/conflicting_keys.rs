@@ -0,0 +1,17 @@
+use base64id::{Base64Id, Key};
+
+const KEY: Key = Key::new(7);
+
+#[derive(Base64Id)]
+#[base64id(sortable, alphabet = base64id::Alphabet::STANDARD)]
+struct MyId(i64);
+
+#[derive(Base64Id)]
+#[base64id(sortable, obfuscate = KEY)]
+struct MyObfuscatedId(i64);
+
+#[derive(Base64Id)]
+#[base64id(obfuscate = KEY)]
+struct MyObfuscatedDigest([u8; 32]);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use base64id::Base64Id;
+
+#[derive(Base64Id)]
+enum MyId {
+    A(i64),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use base64id::Base64Id;
+
+#[derive(Base64Id)]
+#[base64id(Deserialize(accept_integer))]
+struct MyBytesId([u8; 8]);
+
+#[derive(Base64Id)]
+#[base64id(Deserialize(accept_float))]
+struct MyId(i64);
+
+fn main() {}
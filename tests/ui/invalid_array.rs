@@ -0,0 +1,14 @@
+use base64id::Base64Id;
+
+const LEN: usize = 8;
+
+#[derive(Base64Id)]
+struct MyId([u16; 8]);
+
+#[derive(Base64Id)]
+struct MyEmptyId([u8; 0]);
+
+#[derive(Base64Id)]
+struct MyConstId([u8; LEN]);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use base64id::Base64Id;
+
+#[derive(Base64Id)]
+#[base64id(diesel = Binary)]
+struct MyId(i64);
+
+#[derive(Base64Id)]
+#[base64id(diesel)]
+struct MyWideId(u128);
+
+#[derive(Base64Id)]
+#[base64id(diesel)]
+struct MyBytesId([u8; 16]);
+
+fn main() {}
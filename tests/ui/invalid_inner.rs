@@ -0,0 +1,12 @@
+use base64id::Base64Id;
+
+type DbKey = i64;
+
+#[derive(Base64Id)]
+struct MyId(DbKey);
+
+#[derive(Base64Id)]
+#[base64id(inner = f64)]
+struct MyFloatId(f64);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use base64id::Base64Id;
+
+#[derive(Base64Id)]
+struct MyId(String);
+
+#[derive(Base64Id)]
+struct MyByteId(u8);
+
+fn main() {}
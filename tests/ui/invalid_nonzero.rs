@@ -0,0 +1,10 @@
+use base64id::Base64Id;
+use core::num::{NonZero, NonZeroU8};
+
+#[derive(Base64Id)]
+struct MyId(NonZeroU8);
+
+#[derive(Base64Id)]
+struct MyOtherId(NonZero<usize>);
+
+fn main() {}
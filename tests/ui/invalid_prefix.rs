@@ -0,0 +1,11 @@
+use base64id::Base64Id;
+
+#[derive(Base64Id)]
+#[base64id(prefix = "")]
+struct MyId(i64);
+
+#[derive(Base64Id)]
+#[base64id(prefix = "ü_")]
+struct MyOtherId(i64);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use base64id::Base64Id;
+
+#[derive(Base64Id)]
+#[base64id(unknown, prefix = "")]
+struct MyId(f64);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use base64id::Base64Id;
+
+#[derive(Base64Id)]
+struct MyId {
+    id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use base64id::Base64Id;
+
+#[derive(Base64Id)]
+struct MyId;
+
+fn main() {}
@@ -0,0 +1,7 @@
+use base64id::Base64Id;
+
+#[derive(Base64Id)]
+#[base64id(Serialize, compact, padding = true)]
+struct MyId(i64);
+
+fn main() {}
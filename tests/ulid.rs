@@ -0,0 +1,38 @@
+use base64id::{Base64Id, UlidGenerator, UlidParts};
+use core::cell::Cell;
+use core::str::FromStr;
+
+const NOW: u64 = 1_700_000_000_000;
+
+#[derive(Base64Id, Debug)]
+#[base64id(sortable)]
+struct EventId(u128);
+
+#[derive(Base64Id, Debug)]
+struct TraceId(i128);
+
+#[test]
+fn generates_sortable_derived_ids() {
+    let now = Cell::new(NOW);
+    let mut generator = UlidGenerator::new(|| now.get(), rand::random::<u64>);
+
+    let mut encoded = Vec::new();
+    for step in 0..100 {
+        now.set(NOW + step / 10);
+        encoded.push(generator.generate::<EventId>().to_string());
+    }
+
+    assert!(encoded.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn timestamp_of_derived_ids() {
+    let mut generator = UlidGenerator::new(|| NOW, || 7);
+
+    let id = EventId::from_str(&generator.generate::<EventId>().to_string()).unwrap();
+    assert_eq!(UlidParts::decompose(id).timestamp, NOW);
+
+    let id: TraceId = generator.generate();
+    assert!(id.0 > 0);
+    assert_eq!(UlidParts::decompose(id).timestamp, NOW);
+}